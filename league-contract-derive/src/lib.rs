@@ -0,0 +1,320 @@
+//! Derive macros for the league contract
+//!
+//! The game data structs inside `game_module::game_types` all need the same glue code.
+//! The JSON given by the caller is deserialized into the struct and stored as borsh.
+//! For the views it goes the other way round.
+//! Writing this by hand for every game type is boring, so it is generated here.
+
+use proc_macro::TokenStream;
+use proc_macro2::{Ident, TokenStream as TokenStream2};
+use quote::quote;
+use syn::{
+    parse_macro_input, Data, DeriveInput, Field, Fields, GenericArgument, Lit, Meta, NestedMeta,
+    PathArguments, Type,
+};
+
+/// Derive `game_module::GameData` for a game data struct
+///
+/// The struct has to implement the borsh and serde traits, too.
+/// It also needs `#[serde(deny_unknown_fields)]`, as the schema does not allow other fields either.
+/// `game_module::GameSchema` is derived alongside as the structure of the data is its schema.
+/// To be selectable by a league, the struct still has to be listed in `register_game_types!`.
+///
+/// The fields can be annotated with `#[game_data(...)]`:
+/// - `swap = "other_field"` for data per player. It gets swapped with the other field if the
+///   caller gave the player names in the other order than the league stores them.
+/// - `minimum = 1` and `maximum = 10` for numbers. Data outside the range is rejected.
+#[proc_macro_derive(GameData, attributes(game_data))]
+pub fn derive_game_data(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    match game_data_impl(&input) {
+        Ok(tokens) => tokens.into(),
+        Err(e) => e.to_compile_error().into(),
+    }
+}
+
+/// Derive `game_module::GameSchema` for types nested inside game data, e.g. enums
+#[proc_macro_derive(GameSchema, attributes(game_data))]
+pub fn derive_game_schema(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    match schema_impl(&input) {
+        Ok(tokens) => tokens.into(),
+        Err(e) => e.to_compile_error().into(),
+    }
+}
+
+/// The parsed `#[game_data(...)]` attributes of a field
+#[derive(Default)]
+struct FieldOptions {
+    swap: Option<Ident>,
+    minimum: Option<Lit>,
+    maximum: Option<Lit>,
+}
+
+impl FieldOptions {
+    fn parse(field: &Field) -> syn::Result<Self> {
+        let mut options = FieldOptions::default();
+        for attr in field.attrs.iter().filter(|a| a.path.is_ident("game_data")) {
+            let list = match attr.parse_meta()? {
+                Meta::List(list) => list,
+                other => return Err(syn::Error::new_spanned(other, "expected #[game_data(...)]")),
+            };
+            for nested in list.nested.iter() {
+                let nv = match nested {
+                    NestedMeta::Meta(Meta::NameValue(nv)) => nv,
+                    other => return Err(syn::Error::new_spanned(other, "expected `key = value`")),
+                };
+                if nv.path.is_ident("swap") {
+                    match &nv.lit {
+                        Lit::Str(s) => options.swap = Some(s.parse()?),
+                        other => return Err(syn::Error::new_spanned(other, "expected field name")),
+                    }
+                } else if nv.path.is_ident("minimum") {
+                    options.minimum = Some(nv.lit.clone());
+                } else if nv.path.is_ident("maximum") {
+                    options.maximum = Some(nv.lit.clone());
+                } else {
+                    return Err(syn::Error::new_spanned(
+                        &nv.path,
+                        "unknown game_data option",
+                    ));
+                }
+            }
+        }
+        Ok(options)
+    }
+}
+
+/// Get the named fields of a struct. A unit struct has no fields at all.
+fn named_fields(input: &DeriveInput) -> syn::Result<Vec<&Field>> {
+    match &input.data {
+        Data::Struct(data) => match &data.fields {
+            Fields::Named(fields) => Ok(fields.named.iter().collect()),
+            Fields::Unit => Ok(Vec::new()),
+            Fields::Unnamed(_) => Err(syn::Error::new_spanned(
+                &input.ident,
+                "GameData needs named fields",
+            )),
+        },
+        _ => Err(syn::Error::new_spanned(
+            &input.ident,
+            "GameData can only be derived for structs",
+        )),
+    }
+}
+
+/// Returns the inner type if the given type is an `Option`
+fn option_inner(ty: &Type) -> Option<&Type> {
+    if let Type::Path(path) = ty {
+        let segment = path.path.segments.last()?;
+        if segment.ident == "Option" {
+            if let PathArguments::AngleBracketed(args) = &segment.arguments {
+                if let Some(GenericArgument::Type(inner)) = args.args.first() {
+                    return Some(inner);
+                }
+            }
+        }
+    }
+    None
+}
+
+/// Check if the struct is annotated with `#[serde(deny_unknown_fields)]`
+fn denies_unknown_fields(input: &DeriveInput) -> bool {
+    input
+        .attrs
+        .iter()
+        .filter(|attr| attr.path.is_ident("serde"))
+        .filter_map(|attr| match attr.parse_meta() {
+            Ok(Meta::List(list)) => Some(list),
+            _ => None,
+        })
+        .flat_map(|list| list.nested.into_iter())
+        .any(|nested| {
+            matches!(nested, NestedMeta::Meta(Meta::Path(path)) if path.is_ident("deny_unknown_fields"))
+        })
+}
+
+fn game_data_impl(input: &DeriveInput) -> syn::Result<TokenStream2> {
+    let name = &input.ident;
+    let title = name.to_string();
+    // Otherwise serde would silently drop the fields the schema rejects
+    if !denies_unknown_fields(input) {
+        return Err(syn::Error::new_spanned(
+            name,
+            "GameData needs #[serde(deny_unknown_fields)]",
+        ));
+    }
+
+    let mut first = Vec::new();
+    let mut second = Vec::new();
+    let mut checks = Vec::new();
+    for field in named_fields(input)? {
+        let ident = field.ident.as_ref().unwrap();
+        let options = FieldOptions::parse(field)?;
+        if let Some(partner) = options.swap {
+            first.push(ident.clone());
+            second.push(partner);
+        }
+        // Serde cannot check the value ranges, so it is done right after deserialization
+        let mut conditions = Vec::new();
+        if let Some(min) = &options.minimum {
+            conditions.push(quote!(*value >= #min));
+        }
+        if let Some(max) = &options.maximum {
+            conditions.push(quote!(*value <= #max));
+        }
+        if !conditions.is_empty() {
+            checks.push(match option_inner(&field.ty) {
+                Some(_) => quote! {
+                    if let Some(value) = &obj.#ident {
+                        if !(#(#conditions)&&*) {
+                            return None;
+                        }
+                    }
+                },
+                None => quote! {
+                    let value = &obj.#ident;
+                    if !(#(#conditions)&&*) {
+                        return None;
+                    }
+                },
+            });
+        }
+    }
+
+    let schema = schema_impl(input)?;
+    Ok(quote! {
+        #schema
+
+        impl crate::game_module::GameData for #name {
+            fn convert(data: &str, swapped: bool) -> Option<Vec<u8>> {
+                // Serde would also accept a struct given as an array, the schema only knows objects
+                let value = ::serde_json::from_str::<::serde_json::Value>(data).ok()?;
+                if !value.is_object() {
+                    return None;
+                }
+                #[allow(unused_mut)]
+                let mut obj = ::serde_json::from_value::<#name>(value).ok()?;
+                #(#checks)*
+                if swapped {
+                    #(::std::mem::swap(&mut obj.#first, &mut obj.#second);)*
+                }
+                ::near_sdk::borsh::to_vec(&obj).ok()
+            }
+
+            fn convert_back(data: &[u8]) -> ::serde_json::Value {
+                let obj = <#name as ::near_sdk::borsh::BorshDeserialize>::try_from_slice(data).unwrap();
+                ::serde_json::to_value(&obj).unwrap()
+            }
+
+            fn structure() -> ::serde_json::Value {
+                let mut schema = <#name as crate::game_module::GameSchema>::schema();
+                schema["$schema"] = ::serde_json::json!("http://json-schema.org/draft-07/schema#");
+                schema["title"] = ::serde_json::json!(#title);
+                schema
+            }
+        }
+    })
+}
+
+fn schema_impl(input: &DeriveInput) -> syn::Result<TokenStream2> {
+    let name = &input.ident;
+    let body = match &input.data {
+        Data::Enum(data) => enum_schema(data)?,
+        _ => struct_schema(named_fields(input)?)?,
+    };
+    Ok(quote! {
+        impl crate::game_module::GameSchema for #name {
+            fn schema() -> ::serde_json::Value {
+                #body
+            }
+        }
+    })
+}
+
+/// A struct is an object with a property per field. Only `Option` fields are not required.
+fn struct_schema(fields: Vec<&Field>) -> syn::Result<TokenStream2> {
+    let mut properties = Vec::new();
+    let mut required = Vec::new();
+    for field in fields {
+        let ident = field.ident.as_ref().unwrap().to_string();
+        let options = FieldOptions::parse(field)?;
+        let (ty, optional) = match option_inner(&field.ty) {
+            Some(inner) => (inner, true),
+            None => (&field.ty, false),
+        };
+        let minimum = options
+            .minimum
+            .map(|min| quote!(schema["minimum"] = ::serde_json::json!(#min);));
+        let maximum = options
+            .maximum
+            .map(|max| quote!(schema["maximum"] = ::serde_json::json!(#max);));
+        let wrap = if optional {
+            quote!(::serde_json::json!({"anyOf": [schema, {"type": "null"}]}))
+        } else {
+            required.push(ident.clone());
+            quote!(schema)
+        };
+        properties.push(quote! {
+            properties.insert(#ident.to_string(), {
+                #[allow(unused_mut)]
+                let mut schema = <#ty as crate::game_module::GameSchema>::schema();
+                #minimum
+                #maximum
+                #wrap
+            });
+        });
+    }
+    Ok(quote! {
+        let mut properties = ::serde_json::Map::new();
+        #(#properties)*
+        ::serde_json::json!({
+            "type": "object",
+            "properties": properties,
+            "required": [#(#required),*],
+            "additionalProperties": false
+        })
+    })
+}
+
+/// An enum follows the serde representation
+///
+/// Unit variants are plain strings.
+/// Newtype variants are objects with the variant name as the only key.
+fn enum_schema(data: &syn::DataEnum) -> syn::Result<TokenStream2> {
+    let mut units = Vec::new();
+    let mut newtypes = Vec::new();
+    for variant in data.variants.iter() {
+        let ident = variant.ident.to_string();
+        match &variant.fields {
+            Fields::Unit => units.push(ident),
+            Fields::Unnamed(fields) if fields.unnamed.len() == 1 => {
+                let ty = &fields.unnamed.first().unwrap().ty;
+                newtypes.push(quote! {
+                    ::serde_json::json!({
+                        "type": "object",
+                        "properties": {#ident: <#ty as crate::game_module::GameSchema>::schema()},
+                        "required": [#ident],
+                        "additionalProperties": false
+                    })
+                });
+            }
+            _ => {
+                return Err(syn::Error::new_spanned(
+                    variant,
+                    "GameSchema supports unit and newtype variants only",
+                ))
+            }
+        }
+    }
+    let mut alternatives = Vec::new();
+    if !units.is_empty() {
+        alternatives.push(quote!(::serde_json::json!({"enum": [#(#units),*]})));
+    }
+    alternatives.extend(newtypes);
+    if alternatives.len() == 1 {
+        Ok(alternatives.pop().unwrap())
+    } else {
+        Ok(quote!(::serde_json::json!({"oneOf": [#(#alternatives),*]})))
+    }
+}
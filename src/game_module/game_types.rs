@@ -1,34 +1,55 @@
 //! Contains the definition of different game types
+//!
+//! A new game type is just a struct deriving `GameData` next to the borsh and serde traits.
+//! Types nested inside of it need to derive `GameSchema`.
+//! Unknown fields are denied, as the schema does not allow additional properties either.
+//! Afterwards it has to be registered in `game_module` to appear in the `GameType` enum.
 
+use league_contract_derive::{GameData, GameSchema};
 use near_sdk::borsh::{self, BorshDeserialize, BorshSerialize};
 use serde::{Deserialize, Serialize};
-use serde_json;
 
 /// A type with no additional data
 ///
 /// This represents the additional data of a standard game.
 /// These ones do not contain any additional data...
-#[derive(BorshSerialize, BorshDeserialize, Serialize, Deserialize)]
+#[derive(BorshSerialize, BorshDeserialize, Serialize, Deserialize, GameData)]
+#[serde(deny_unknown_fields)]
 pub struct StandardGameData {}
 
-impl StandardGameData {
-    /// Converts the json data into a borsh serialization
-    ///
-    /// As a TODO: This shall be reimplemented as a derive macro
-    pub fn convert(data: &String) -> Option<Vec<u8>> {
-        match serde_json::from_str::<StandardGameData>(data) {
-            Ok(obj) => match borsh::to_vec(&obj) {
-                Ok(serialization) => Some(serialization),
-                Err(_) => None,
-            },
-            Err(_) => None,
-        }
-    }
+/// The three playable races of StarCraft II
+#[derive(BorshSerialize, BorshDeserialize, Serialize, Deserialize, GameSchema, Clone, Copy)]
+pub enum Race {
+    Terran,
+    Zerg,
+    Protoss,
+}
 
-    /// Converts the borsh binaries into json again
-    ///
-    /// Same TODO as above
-    pub fn convert_back(data: &Vec<u8>) -> String {
-        serde_json::to_string(&StandardGameData::try_from_slice(data.as_slice()).unwrap()).unwrap()
-    }
+/// The race a player picked before the game
+///
+/// A random pick is resolved by the game into one of the actual races.
+/// The resolved race is noted as well as it is the one which was actually played.
+#[derive(BorshSerialize, BorshDeserialize, Serialize, Deserialize, GameSchema, Clone, Copy)]
+pub enum PickedRace {
+    Terran,
+    Zerg,
+    Protoss,
+    Random(Race),
+}
+
+/// The additional data of a StarCraft II game
+///
+/// `first` and `second` refer to the order of the player names given when adding the game.
+/// The duration is the in-game length in seconds and has to be between one second and a day.
+/// The replay is an optional reference to the replay file, e.g. a link or a hash.
+#[derive(BorshSerialize, BorshDeserialize, Serialize, Deserialize, GameData)]
+#[serde(deny_unknown_fields)]
+pub struct StarcraftGameData {
+    #[game_data(swap = "second_player_race")]
+    pub first_player_race: PickedRace,
+    pub second_player_race: PickedRace,
+    pub map: String,
+    #[game_data(minimum = 1, maximum = 86400)]
+    pub duration: u32,
+    pub replay: Option<String>,
 }
@@ -5,59 +5,177 @@
 
 pub mod game_types;
 
-use crate::game_module::game_types::StandardGameData;
+use crate::game_module::game_types::{StandardGameData, StarcraftGameData};
 use near_sdk::borsh::{self, BorshDeserialize, BorshSerialize};
 use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
 use strum_macros::EnumVariantNames;
 
-/// An enum to describe the game type
+/// The glue between the JSON of the caller and the borsh serialization of a game
 ///
-/// It is used to decide to which game the data shall be deserialized
-#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone, EnumVariantNames)]
-pub enum GameType {
-    StandardGameType,
+/// Do not implement this by hand but derive it with `league_contract_derive::GameData`.
+pub trait GameData {
+    /// Converts the json data into a borsh serialization
+    ///
+    /// Per player data is `swapped` if the caller gave the players in the other order.
+    fn convert(data: &str, swapped: bool) -> Option<Vec<u8>>;
+    /// Converts the borsh binaries into json again
+    fn convert_back(data: &[u8]) -> Value;
+    /// Describes the JSON structure of the data as a JSON Schema document
+    fn structure() -> Value;
+}
+
+/// The JSON Schema of a type used inside the game data
+///
+/// Derive it with `league_contract_derive::GameSchema` for own types like enums.
+/// The basic types are implemented below.
+pub trait GameSchema {
+    /// The schema of the type without any document headers
+    fn schema() -> Value;
+}
+
+impl GameSchema for String {
+    fn schema() -> Value {
+        json!({"type": "string"})
+    }
+}
+
+impl GameSchema for bool {
+    fn schema() -> Value {
+        json!({"type": "boolean"})
+    }
+}
+
+/// Integers are limited by the range of their rust type
+macro_rules! integer_schema {
+    ($($t:ty),*) => {
+        $(impl GameSchema for $t {
+            fn schema() -> Value {
+                json!({"type": "integer", "minimum": <$t>::MIN, "maximum": <$t>::MAX})
+            }
+        })*
+    };
+}
+
+integer_schema!(u8, u16, u32, u64, i8, i16, i32, i64);
+
+/// Registers the game data structs as variants of the `GameType` enum
+///
+/// A derive macro cannot add a variant to an enum defined somewhere else.
+/// Therefore every game type is listed once in here and all the dispatching is generated.
+///
+/// To add a game type, derive `GameData` for its struct in `game_types`
+/// and append a `Variant => Struct` line to the list below.
+/// The variant is the name the callers give as `game_type` when creating a league.
+/// New variants go to the end, as the leagues store the game type by its position.
+macro_rules! register_game_types {
+    ($($variant:ident => $data:ty),* $(,)?) => {
+        /// An enum to describe the game type
+        ///
+        /// It is used to decide to which game the data shall be deserialized
+        #[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone, EnumVariantNames)]
+        pub enum GameType {
+            $($variant,)*
+        }
+
+        impl GameType {
+            /// Convert the JSON `data` into the borsh serialization of this game type
+            pub fn convert(&self, data: &str, swapped: bool) -> Option<Vec<u8>> {
+                match self {
+                    $(GameType::$variant => <$data as GameData>::convert(data, swapped),)*
+                }
+            }
+
+            /// Convert the borsh serialization of this game type back into JSON
+            pub fn convert_back(&self, data: &[u8]) -> Value {
+                match self {
+                    $(GameType::$variant => <$data as GameData>::convert_back(data),)*
+                }
+            }
+
+            /// Retrieve the JSON Schema of this game type
+            pub fn structure(&self) -> Value {
+                match self {
+                    $(GameType::$variant => <$data as GameData>::structure(),)*
+                }
+            }
+        }
+    };
+}
+
+register_game_types! {
+    StandardGameType => StandardGameData,
+    StarcraftGameType => StarcraftGameData,
+}
+
+/// The outcome of a single game
+///
+/// First and second refer to the order of the players in the context, e.g. the `PlayerPair`.
+/// The first two variants keep the byte of the former `first_player_is_winner` flag,
+/// so the games stored before draws existed are still read correctly.
+#[derive(
+    BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone, Copy, PartialEq, Debug,
+)]
+pub enum GameOutcome {
+    SecondPlayerWon,
+    FirstPlayerWon,
+    Draw,
+}
+
+impl GameOutcome {
+    /// Get the same outcome from the view of the swapped players
+    pub fn swapped(self) -> Self {
+        match self {
+            GameOutcome::FirstPlayerWon => GameOutcome::SecondPlayerWon,
+            GameOutcome::SecondPlayerWon => GameOutcome::FirstPlayerWon,
+            GameOutcome::Draw => GameOutcome::Draw,
+        }
+    }
 }
 
 /// The game type
 ///
 /// The contestants are given by the containing `GameMatch`
-/// Here only the winner of the single game is given and the additional serialized data
+/// Here only the outcome of the single game is given and the additional serialized data
 #[derive(BorshDeserialize, BorshSerialize)]
 pub struct Game {
-    first_player_is_winner: bool,
+    outcome: GameOutcome,
     game_data: Vec<u8>,
 }
 
 impl Game {
     /// Create a new game
     ///
-    /// `first_player_is_winner` does exactly what its name is.
-    /// The 'game_type' is the type to decide in which the JSON `data` shall be deserialized
+    /// The `outcome` is seen from the players in the order of the containing `GameMatch`.
+    /// The 'game_type' is the type to decide in which the JSON `data` shall be deserialized.
+    /// As the `data` may contain information per player, it needs to know if the players were `swapped`.
     pub fn new_with_data(
-        first_player_is_winner: bool,
+        outcome: GameOutcome,
         game_type: GameType,
-        data: &String,
+        data: &str,
+        swapped: bool,
     ) -> Option<Self> {
-        let game_data = match game_type {
-            GameType::StandardGameType => StandardGameData::convert(data),
-        }?;
-        Some(Game {
-            first_player_is_winner,
-            game_data,
-        })
+        let game_data = game_type.convert(data, swapped)?;
+        Some(Game { outcome, game_data })
     }
 
-    /// Retrieve if the first player is the winner
-    pub fn first_player_won(&self) -> bool {
-        self.first_player_is_winner
+    /// Create a copy of the game where the other player won
+    ///
+    /// The game data stays the same and a draw stays a draw.
+    pub fn overturned(&self) -> Self {
+        Game {
+            outcome: self.outcome.swapped(),
+            game_data: self.game_data.clone(),
+        }
+    }
+
+    /// Retrieve the outcome of the game
+    pub fn outcome(&self) -> GameOutcome {
+        self.outcome
     }
 
     /// Retrieve the game content as JSON
-    ///
-    /// TODO this will be nested into another json. this looks ugly as string
-    pub fn game_content(&self, game_type: &GameType) -> String {
-        match game_type {
-            GameType::StandardGameType => StandardGameData::convert_back(&self.game_data),
-        }
+    pub fn game_content(&self, game_type: &GameType) -> Value {
+        game_type.convert_back(&self.game_data)
     }
 }
@@ -4,12 +4,17 @@
 //! Each league can have a specific game type with additional meta information of each game.
 //! Game types can still be added in `game_module::game_types`.
 //! They also can be quite generic as long as it is a 1v1 type, e.g. soccer.
-//! A scoreboard and evaluation of the game meta types is not part of the contract
+//! The standings of a league are computed with configurable points and tiebreakers.
+//! An evaluation of the game meta types is not part of the contract
 //!   mostly because it can be done outside, too. (and I postpone it..)
 //!
 //! Per league there are trusted accounts
 //! which can manipulate the league and the actual game matches.
-//! The owner (=creator) of the league may also delete the league.
+//! The owner (initially the creator) of the league may also delete the league.
+//! The ownership can be handed over to another account which has to accept it.
+
+// The league logic lives in `main`, it is not a binary even though it sounds like one
+#![allow(special_module_name)]
 
 extern crate near_sdk;
 
@@ -18,24 +23,41 @@ pub mod main;
 
 use near_sdk::borsh::{self, BorshDeserialize, BorshSerialize};
 use near_sdk::collections::Vector;
-use near_sdk::collections::{LookupMap, LookupSet};
-use near_sdk::{env, near_bindgen, require, AccountId, PanicOnDefault};
+use near_sdk::collections::{LookupMap, UnorderedSet};
+use near_sdk::{env, near_bindgen, require, AccountId, Promise, StorageUsage};
+use serde_json::Value;
 
-use game_module::GameType;
+use game_module::{GameOutcome, GameType};
+use main::bracket::HelperBracketMatch;
 use main::helper::CollectionKeyTuple;
-use main::{League, LeagueProperties, UpgradeableLeagueProperties};
+use main::schedule::HelperMatchday;
+use main::standings::StandingsEntry;
+use main::swiss::HelperSwissRound;
+use main::{
+    DisputeRuling, HelperCorrection, HelperDispute, HelperLeague, HelperPledge, League,
+    LeagueOptions, LeagueProperties, UpgradeableLeagueProperties, MAX_PLAYERS,
+};
 use strum::VariantNames;
 
 /// The smart contract
 ///
 /// The actual smart contract struct
 #[near_bindgen]
-#[derive(BorshDeserialize, BorshSerialize, PanicOnDefault)]
+#[derive(BorshDeserialize, BorshSerialize)]
+#[cfg_attr(not(test), derive(near_sdk::PanicOnDefault))]
 pub struct LeagueContract {
     /// A map of named leagues. The name is given by the key.
     leagues: LookupMap<String, League>,
 }
 
+/// The unit tests panic by hand, as the host panic of `PanicOnDefault` cannot unwind there
+#[cfg(test)]
+impl Default for LeagueContract {
+    fn default() -> Self {
+        panic!("The contract is not initialized")
+    }
+}
+
 #[near_bindgen]
 impl LeagueContract {
     /// Initialize the contract
@@ -56,6 +78,15 @@ impl LeagueContract {
     /// He has to give a `league_name` and a list of trusted `accounts` who may also create call to this league.
     /// With `best_of` and `game_type` all necessary league properties were given.
     /// Finally the a list of `players` in the league were also needed.
+    /// Everything else is optional and can be given by `options`, e.g. the `DrawRule`.
+    /// With a `Registration` in the options the `players` are only the first entries,
+    /// more join by themselves until the owner starts the league.
+    /// A league created as a draft waits for the owner to open the registration or to start it.
+    ///
+    /// The attached deposit is the pledge of the league.
+    /// It has to cover the storage of the league including an estimation for all matches.
+    /// Whatever is not needed for the storage is refunded on deletion.
+    #[payable]
     pub fn create_league(
         &mut self,
         league_name: String,
@@ -63,9 +94,13 @@ impl LeagueContract {
         accounts: Vec<AccountId>,
         best_of: u8,
         game_type: GameType,
+        options: Option<LeagueOptions>,
     ) {
         require!(best_of % 2 == 1, "best_of number should be odd");
-        require!(players.len() > 2, "League needs at least 3 participant");
+        require!(
+            players.len() <= MAX_PLAYERS as usize,
+            "League cannot have more than 128 players"
+        );
         require!(
             league_name.len() > 2,
             "League name must be at least 3 chars long"
@@ -75,10 +110,53 @@ impl LeagueContract {
             "League with that name already exists"
         );
 
+        let initial_storage = env::storage_usage();
         // Create unique keys for the collections inside the league
         let keys = CollectionKeyTuple::new(&league_name);
 
-        let prop = UpgradeableLeagueProperties::V1(LeagueProperties { best_of, game_type });
+        let options = options.unwrap_or_default();
+        options.points.assert_valid();
+        let draw_rule = options
+            .draw_rule
+            .unwrap_or_else(|| options.format.default_draw_rule());
+        require!(
+            !options.strict_schedule || options.format.has_schedule(),
+            "Only a round robin league can have a strict schedule"
+        );
+        require!(
+            !options.withdrawal_policy.drop_remaining || !options.format.is_knockout(),
+            "Knockout formats need the remaining matches of a withdrawn player to be forfeited"
+        );
+        require!(
+            (options.registration.is_none() && !options.draft) || options.groups.is_none(),
+            "The groups of a league starting later are assigned when it starts"
+        );
+        // The players are checked against the other options once the league starts
+        let registration = options.registration.is_some();
+        let draft = options.draft;
+        let groups = options.groups;
+        let prop = UpgradeableLeagueProperties::V2(LeagueProperties {
+            best_of,
+            game_type,
+            draw_rule,
+            points: options.points,
+            tiebreakers: options
+                .tiebreakers
+                .unwrap_or_else(|| options.format.default_tiebreakers()),
+            format: options.format,
+            groups: Vec::new(),
+            best_of_overrides: options.best_of_overrides,
+            strict_schedule: options.strict_schedule,
+            deadline: options.deadline.map(|deadline| deadline.0),
+            matchday_deadlines: options
+                .matchday_deadlines
+                .iter()
+                .map(|deadline| deadline.0)
+                .collect(),
+            forfeit_policy: options.forfeit_policy,
+            withdrawal_policy: options.withdrawal_policy,
+            registration: options.registration,
+        });
 
         // Convert the player standard vec to a NEAR collection for the blockchain
         let mut p = Vector::new(keys.get_players_key());
@@ -87,31 +165,174 @@ impl LeagueContract {
         }
         // Do the same with the account ids. Also check if the caller does not mention himself.
         // The caller is assumed to be trusted and has as owner even more rights.
-        let mut a = LookupSet::new(keys.get_trusted_key());
+        let mut a = UnorderedSet::new(keys.get_trusted_key());
         let caller = &env::predecessor_account_id();
         for account in accounts {
             if account != *caller {
                 a.insert(&account);
             }
         }
-        let l = League::new(keys, prop, p, a);
+        let mut l = League::new(keys, prop, p, a, env::attached_deposit());
+        match (draft, registration) {
+            // The owner decides when a draft goes on
+            (true, _) => {}
+            (false, true) => l.open_registration(),
+            (false, false) => l.start(groups),
+        }
+        self.leagues.insert(&league_name, &l);
+        l.account_storage(initial_storage);
+        require!(
+            l.pledge_covers(l.reserved_storage()),
+            "Attached deposit does not cover the storage of the league"
+        );
         self.leagues.insert(&league_name, &l);
     }
 
+    /// CALL: Join a league in registration
+    ///
+    /// The caller joins the league by the name `league_name` as a player with the display `name`.
+    /// The account is linked to the player, so it may report the games of its own matches.
+    /// The registration may be limited by a cap of players and a deadline.
+    pub fn join_league(&mut self, league_name: String, name: String) {
+        let league = self.leagues.get(&league_name);
+        require!(league.is_some(), "League does not exist");
+        let mut league = league.unwrap();
+        let initial_storage = env::storage_usage();
+        league.join(name.clone());
+        self.save_league(&league_name, &mut league, initial_storage);
+        env::log_str(&format!(
+            "{} joined league {} as {}",
+            env::predecessor_account_id(),
+            league_name,
+            name
+        ));
+    }
+
+    /// CALL: Remove an entry of a league in registration
+    ///
+    /// The caller has to be the owner of the league by the name `league_name`.
+    pub fn remove_entry(&mut self, league_name: String, player: String) {
+        let league = self.leagues.get(&league_name);
+        require!(league.is_some(), "League does not exist");
+        let mut league = league.unwrap();
+        let initial_storage = env::storage_usage();
+        league.remove_entry(&player);
+        self.save_league(&league_name, &mut league, initial_storage);
+        env::log_str(&format!(
+            "{} was removed from league {}",
+            player, league_name
+        ));
+    }
+
+    /// CALL: Start a league in registration
+    ///
+    /// The caller has to be the owner of the league by the name `league_name`.
+    /// The roster is frozen and the matches can be played from now on.
+    /// A league with groups may get the group of every player in the order of the players,
+    /// otherwise they are distributed in snake order.
+    /// The pledge has to cover the storage of the expected matches now.
+    pub fn start_league(&mut self, league_name: String, groups: Option<Vec<u8>>) {
+        let league = self.leagues.get(&league_name);
+        require!(league.is_some(), "League does not exist");
+        let mut league = league.unwrap();
+        let initial_storage = env::storage_usage();
+        league.start(groups);
+        self.save_league(&league_name, &mut league, initial_storage);
+        require!(
+            league.pledge_covers(league.reserved_storage()),
+            "The pledge of the league does not cover its matches, please top up"
+        );
+        env::log_str(&format!("League {} started", league_name));
+    }
+
+    /// CALL: Open the registration of a draft league
+    ///
+    /// The caller has to be the owner of the league by the name `league_name`.
+    /// The league needs a `Registration` in its options.
+    pub fn open_registration(&mut self, league_name: String) {
+        self.change_lifecycle(&league_name, |league| league.open_registration());
+    }
+
+    /// CALL: Pause a running league
+    ///
+    /// The caller has to be the owner of the league by the name `league_name`.
+    /// No matches can be played until it is resumed, e.g. while a dispute is resolved.
+    pub fn pause_league(&mut self, league_name: String) {
+        self.change_lifecycle(&league_name, |league| league.pause());
+    }
+
+    /// CALL: Resume a paused league
+    ///
+    /// The caller has to be the owner of the league by the name `league_name`.
+    pub fn resume_league(&mut self, league_name: String) {
+        self.change_lifecycle(&league_name, |league| league.resume());
+    }
+
+    /// CALL: Close a league
+    ///
+    /// The caller has to be the owner of the league by the name `league_name`.
+    /// The league won't be closed if it is not finished except it is explicitely wished by setting
+    /// `force` to true! Open disputes have to be resolved before.
+    /// No results of the league change anymore afterwards.
+    pub fn close_league(&mut self, league_name: String, force: bool) {
+        self.change_lifecycle(&league_name, |league| league.close(force));
+    }
+
+    /// CALL: Archive a closed league
+    ///
+    /// The caller has to be the owner of the league by the name `league_name`.
+    /// Nothing of the league changes anymore afterwards, it can only be deleted.
+    pub fn archive_league(&mut self, league_name: String) {
+        self.change_lifecycle(&league_name, |league| league.archive());
+    }
+
     /// CALL: Delete a league
     ///
     /// The caller has to be the owner of the league by the name `league_name`.
     /// The league won't be deleted if it is not finished except it is explicitely wished by setting
     /// `force` to true!
+    /// The pledge is refunded to the owner as far as it is not needed for remaining storage.
+    /// The league does not remember who paid, so the current owner gets the top ups and
+    /// the pledge of a previous owner, too.
     pub fn delete_league(&mut self, league_name: String, force: bool) {
         // Cannot remove yet
         let league = self.leagues.get(&league_name);
         require!(league.is_some(), "League to delete not found");
         // safe to use unwrap now. Could be done in match pattern but I like this more for require!
-        let league = league.unwrap();
+        let mut league = league.unwrap();
         require!(league.caller_is_owner(), "You may not delete the league");
-        require!(league.is_finished() || force, "League is not finished yet");
+        require!(
+            league.is_closed() || league.is_finished() || force,
+            "League is not finished yet"
+        );
+        let initial_storage = env::storage_usage();
+        league.clear();
         self.leagues.remove(&league_name);
+        let refund = league.refund(initial_storage - env::storage_usage());
+        if refund > 0 {
+            Promise::new(league.owner()).transfer(refund);
+        }
+        env::log_str(&format!(
+            "Refunded {} of the pledge of league {} to {}",
+            refund,
+            league_name,
+            league.owner()
+        ));
+    }
+
+    /// CALL: Top up the pledge of a league
+    ///
+    /// Anybody may attach a deposit to the league by the name `league_name`.
+    /// It is needed if the storage of the league grows beyond its pledge.
+    /// The whole pledge is refunded to the owner on deletion, so topping up is a gift to the league.
+    #[payable]
+    pub fn top_up_pledge(&mut self, league_name: String) {
+        require!(env::attached_deposit() > 0, "Attach a deposit to top up");
+        let league = self.leagues.get(&league_name);
+        require!(league.is_some(), "League does not exist");
+        let mut league = league.unwrap();
+        league.top_up(env::attached_deposit());
+        self.leagues.insert(&league_name, &league);
     }
 
     /// CALL: Add a game to a league
@@ -119,154 +340,709 @@ impl LeagueContract {
     /// The caller has to be a trusted account of the league by the name `league_name`.
     /// The game with the given `game_data` and the players given by `player_names` will be added.
     /// The `game_data` has to be deserializable to the type given by the league's `GameType`.
-    /// Also the winner has to be given by explicitely saying if the `first_in_tuple_won` or not...
+    /// Also the `outcome` has to be given, where first and second refer to the order in `player_names`.
     pub fn add_game(
         &mut self,
         league_name: String,
         player_names: (String, String),
-        first_in_tuple_won: bool,
+        outcome: GameOutcome,
         game_data: String,
     ) {
         require!(player_names.0 != player_names.1, "Need different players");
         let league = self.leagues.get(&league_name);
         require!(league.is_some(), "League does not exist");
         let mut league = league.unwrap();
-        league.add_game(&player_names, first_in_tuple_won, &game_data);
-        self.leagues.insert(&league_name, &league);
+        let initial_storage = env::storage_usage();
+        league.add_game(&player_names, outcome, &game_data);
+        self.save_league(&league_name, &mut league, initial_storage);
     }
 
-    /// VIEW: Retrieve a list of all implemented game types
+    /// CALL: Link an account to a player of a league
     ///
-    /// Now the frontend (or other user) can check which types can be used to create a league
-    /// This could be actually static but does a static function make sense for a contract?!?
-    pub fn get_game_types(&self) -> Vec<&'static str> {
-        GameType::VARIANTS.to_vec()
+    /// The owner and the trusted accounts of the league by the name `league_name` may link any `account_id`
+    /// to the player by the name `player`, replacing the account linked before.
+    /// Anybody else may ask to link their own account to a player without one,
+    /// the request is shown in the league until the owner or a trusted account links the account.
+    /// An account can only be linked to one player of a league.
+    pub fn link_account(&mut self, league_name: String, player: String, account_id: AccountId) {
+        let league = self.leagues.get(&league_name);
+        require!(league.is_some(), "League does not exist");
+        let mut league = league.unwrap();
+        let initial_storage = env::storage_usage();
+        let linked = league.link_account(&player, account_id.clone());
+        self.save_league(&league_name, &mut league, initial_storage);
+        if linked {
+            env::log_str(&format!(
+                "{} linked to {} in league {}",
+                account_id, player, league_name
+            ));
+        } else {
+            env::log_str(&format!(
+                "{} asked to be linked to {} in league {}",
+                account_id, player, league_name
+            ));
+        }
     }
 
-    /// VIEW: Retrieve the structure of a game type
+    /// CALL: Report a game of the caller's own match in a league
     ///
-    /// The frontend can build a form to add games to a league.
-    /// A JSON of the structure will be returned
-    /// TODO just a stub right now. The StandardGameType is the only type and contains nothing
-    pub fn get_game_structure(&self, game_type: GameType) -> String {
-        match game_type {
-            GameType::StandardGameType => "".to_string(),
-        }
+    /// The caller has to be linked to one of the players given by `player_names`.
+    /// The game is given like for `add_game`, but stays pending until the opponent confirms it.
+    pub fn report_game(
+        &mut self,
+        league_name: String,
+        player_names: (String, String),
+        outcome: GameOutcome,
+        game_data: String,
+    ) {
+        require!(player_names.0 != player_names.1, "Need different players");
+        let league = self.leagues.get(&league_name);
+        require!(league.is_some(), "League does not exist");
+        let mut league = league.unwrap();
+        let initial_storage = env::storage_usage();
+        league.report_game(&player_names, outcome, &game_data);
+        self.save_league(&league_name, &mut league, initial_storage);
     }
 
-    /// VIEW: Get the current league progress
+    /// CALL: Confirm the pending game between the players given by `player_names`
     ///
-    /// Retrieve the leagues progress as a JSON
-    pub fn get_league(&self, league_name: String) -> String {
+    /// The caller has to be linked to the opponent of the reporting player
+    /// or be a trusted account of the league by the name `league_name`.
+    pub fn confirm_game(&mut self, league_name: String, player_names: (String, String)) {
+        require!(player_names.0 != player_names.1, "Need different players");
         let league = self.leagues.get(&league_name);
         require!(league.is_some(), "League does not exist");
-        league.unwrap().summarize_league()
+        let mut league = league.unwrap();
+        let initial_storage = env::storage_usage();
+        league.confirm_game(&player_names);
+        self.save_league(&league_name, &mut league, initial_storage);
     }
-}
-
-#[cfg(all(test, not(target_arch = "wasm32")))]
-mod tests {
-    use crate::game_module::GameType;
-    use crate::game_module::GameType::StandardGameType;
-    use crate::LeagueContract;
-    use near_sdk::test_utils::{accounts, VMContextBuilder};
-    use near_sdk::testing_env;
 
-    fn create_context() -> VMContextBuilder {
-        let mut context = VMContextBuilder::new();
-        context
-            .current_account_id(accounts(0))
-            .signer_account_id(accounts(0))
-            .predecessor_account_id(accounts(0));
-        testing_env!(context.build());
-        context
+    /// CALL: Reject the pending game between the players given by `player_names`
+    ///
+    /// The caller has to be linked to one of the players
+    /// or be a trusted account of the league by the name `league_name`.
+    pub fn reject_game(&mut self, league_name: String, player_names: (String, String)) {
+        require!(player_names.0 != player_names.1, "Need different players");
+        let league = self.leagues.get(&league_name);
+        require!(league.is_some(), "League does not exist");
+        let mut league = league.unwrap();
+        let initial_storage = env::storage_usage();
+        league.reject_game(&player_names);
+        self.save_league(&league_name, &mut league, initial_storage);
     }
 
-    /// Test that checks if the default implementation panics as expected
-    #[test]
-    #[should_panic(expected = "The contract is not initialized")]
-    fn test_default() {
-        let _context = create_context();
+    /// CALL: Record a forfeit in a league
+    ///
+    /// The caller has to be a trusted account of the league by the name `league_name`.
+    /// The match between the players given by `player_names` is finished without adding a game.
+    /// Only a match without any game can be forfeited.
+    /// The `winner` is the name of the player winning by walkover or `None` if both forfeited.
+    /// The `reason` is noted with the match, e.g. that somebody did not show up.
+    pub fn record_forfeit(
+        &mut self,
+        league_name: String,
+        player_names: (String, String),
+        winner: Option<String>,
+        reason: String,
+    ) {
+        require!(player_names.0 != player_names.1, "Need different players");
+        let league = self.leagues.get(&league_name);
+        require!(league.is_some(), "League does not exist");
+        let mut league = league.unwrap();
+        let initial_storage = env::storage_usage();
+        league.record_forfeit(&player_names, winner, reason);
+        self.save_league(&league_name, &mut league, initial_storage);
+    }
 
-        let _contract = LeagueContract::default();
+    /// CALL: Correct a recorded game of a league
+    ///
+    /// The caller has to be a trusted account of the league by the name `league_name`.
+    /// The game at `game` (starting with 0) in the match between the players given by `player_names`
+    /// is replaced by the `outcome` and the `game_data`, where first and second refer to the order in `player_names`.
+    /// The `series` tells which meeting of the players is meant, as shown by the league view.
+    /// The original game is kept in the audit trail.
+    pub fn edit_game(
+        &mut self,
+        league_name: String,
+        player_names: (String, String),
+        series: u8,
+        game: u8,
+        outcome: GameOutcome,
+        game_data: String,
+    ) {
+        require!(player_names.0 != player_names.1, "Need different players");
+        let league = self.leagues.get(&league_name);
+        require!(league.is_some(), "League does not exist");
+        let mut league = league.unwrap();
+        let initial_storage = env::storage_usage();
+        league.edit_game(&player_names, series, game, outcome, &game_data);
+        self.save_league(&league_name, &mut league, initial_storage);
+        env::log_str(&format!(
+            "{} corrected game {} between {} and {} in league {}",
+            env::predecessor_account_id(),
+            game,
+            player_names.0,
+            player_names.1,
+            league_name
+        ));
     }
 
-    /// Test that checks that the init function functions fine.
-    #[test]
-    fn test_new() {
-        let _context = create_context();
+    /// CALL: Remove the last recorded game of a match in a league
+    ///
+    /// The caller has to be a trusted account of the league by the name `league_name`.
+    /// The match is the `series`-th meeting of the players given by `player_names`.
+    /// A finished match is open again afterwards, unless later matches depend on it.
+    /// The removed game is kept in the audit trail.
+    pub fn remove_last_game(
+        &mut self,
+        league_name: String,
+        player_names: (String, String),
+        series: u8,
+    ) {
+        require!(player_names.0 != player_names.1, "Need different players");
+        let league = self.leagues.get(&league_name);
+        require!(league.is_some(), "League does not exist");
+        let mut league = league.unwrap();
+        let initial_storage = env::storage_usage();
+        league.remove_last_game(&player_names, series);
+        self.save_league(&league_name, &mut league, initial_storage);
+        env::log_str(&format!(
+            "{} removed the last game between {} and {} in league {}",
+            env::predecessor_account_id(),
+            player_names.0,
+            player_names.1,
+            league_name
+        ));
+    }
 
-        let _contract = LeagueContract::new();
+    /// CALL: Open a dispute on a match or a game of a league
+    ///
+    /// The caller has to be linked to one of the players given by `player_names`
+    /// or be a trusted account of the league by the name `league_name`.
+    /// The `series` tells which meeting of the players is meant and the `game` its index,
+    /// without a game the whole match is disputed.
+    /// The `reason` and an optional reference to the `evidence` are kept in the dispute log.
+    /// The match is frozen until the dispute is resolved.
+    /// Returns the id of the dispute.
+    pub fn open_dispute(
+        &mut self,
+        league_name: String,
+        player_names: (String, String),
+        series: u8,
+        game: Option<u8>,
+        reason: String,
+        evidence: Option<String>,
+    ) -> u64 {
+        require!(player_names.0 != player_names.1, "Need different players");
+        let league = self.leagues.get(&league_name);
+        require!(league.is_some(), "League does not exist");
+        let mut league = league.unwrap();
+        let initial_storage = env::storage_usage();
+        let id = league.open_dispute(&player_names, series, game, reason, evidence);
+        self.save_league(&league_name, &mut league, initial_storage);
+        env::log_str(&format!(
+            "{} opened dispute {} between {} and {} in league {}",
+            env::predecessor_account_id(),
+            id,
+            player_names.0,
+            player_names.1,
+            league_name
+        ));
+        id
     }
 
-    /// Test the creation of a league
-    #[test]
-    fn test_create_league() {
-        let _context = create_context();
+    /// CALL: Resolve a dispute of a league
+    ///
+    /// The caller has to be a trusted account of the league by the name `league_name`.
+    /// The open dispute with the `dispute_id` is resolved by the `ruling`,
+    /// which upholds the result, overturns it or lets the disputed games be replayed.
+    pub fn resolve_dispute(&mut self, league_name: String, dispute_id: u64, ruling: DisputeRuling) {
+        let league = self.leagues.get(&league_name);
+        require!(league.is_some(), "League does not exist");
+        let mut league = league.unwrap();
+        let initial_storage = env::storage_usage();
+        league.resolve_dispute(dispute_id, ruling);
+        self.save_league(&league_name, &mut league, initial_storage);
+        env::log_str(&format!(
+            "{} resolved dispute {} in league {} by {:?}",
+            env::predecessor_account_id(),
+            dispute_id,
+            league_name,
+            ruling
+        ));
+    }
 
-        let mut contract = LeagueContract::new();
-        let players = vec!["Alice".to_string(), "Bob".to_string(), "Charly".to_string()];
-        let accs = vec![accounts(0), accounts(1)];
-        contract.create_league("SomeLeague".to_string(), players, accs, 3, StandardGameType);
+    /// CALL: Withdraw a player from a league
+    ///
+    /// Only the owner of the league by the name `league_name` may do this.
+    /// The player by the name `player` cannot play anymore.
+    /// The league's `WithdrawalPolicy` decides what happens with the played and the remaining matches.
+    pub fn withdraw_player(&mut self, league_name: String, player: String) {
+        let league = self.leagues.get(&league_name);
+        require!(league.is_some(), "League does not exist");
+        let mut league = league.unwrap();
+        let initial_storage = env::storage_usage();
+        league.withdraw_player(&player);
+        self.save_league(&league_name, &mut league, initial_storage);
+        env::log_str(&format!("{} withdrew from league {}", player, league_name));
     }
 
-    /// Test the expected panic of a name collision in leagues
-    #[test]
-    #[should_panic(expected = "League with that name already exists")]
-    fn test_create_league_twice() {
-        let _context = create_context();
+    /// CALL: Forfeit the overdue matches of a league
+    ///
+    /// The caller has to be a trusted account of the league by the name `league_name`.
+    /// Every match which is not finished before its deadline is forfeited according to the league's `ForfeitPolicy`.
+    /// Returns the number of forfeited matches.
+    pub fn forfeit_overdue_matches(&mut self, league_name: String) -> u32 {
+        let league = self.leagues.get(&league_name);
+        require!(league.is_some(), "League does not exist");
+        let mut league = league.unwrap();
+        let initial_storage = env::storage_usage();
+        let forfeited = league.forfeit_overdue_matches();
+        self.save_league(&league_name, &mut league, initial_storage);
+        env::log_str(&format!(
+            "{} forfeited {} overdue matches of league {}",
+            env::predecessor_account_id(),
+            forfeited,
+            league_name
+        ));
+        forfeited
+    }
 
-        let mut contract = LeagueContract::new();
-        let players = vec!["Alice".to_string(), "Bob".to_string(), "Charly".to_string()];
-        let accs = vec![accounts(0), accounts(1)];
-        contract.create_league(
-            "SomeLeague".to_string(),
-            players.clone(),
-            accs.clone(),
-            3,
-            StandardGameType,
+    /// CALL: Propose a new owner of a league
+    ///
+    /// Only the owner of the league by the name `league_name` may do this.
+    /// The ownership is transferred as soon as `account_id` accepts it.
+    pub fn propose_owner(&mut self, league_name: String, account_id: AccountId) {
+        let league = self.leagues.get(&league_name);
+        require!(league.is_some(), "League does not exist");
+        let mut league = league.unwrap();
+        let initial_storage = env::storage_usage();
+        require!(
+            league.caller_is_owner(),
+            "Only the owner may transfer the league"
         );
-        contract.create_league("SomeLeague".to_string(), players, accs, 3, StandardGameType);
+        league.propose_owner(&account_id);
+        self.save_league(&league_name, &mut league, initial_storage);
+        env::log_str(&format!(
+            "{} proposed {} as owner of league {}",
+            env::predecessor_account_id(),
+            account_id,
+            league_name
+        ));
     }
 
-    /// Test a forced deletion of a league
-    #[test]
-    fn test_force_delete_unfinished_league() {
-        let _context = create_context();
+    /// CALL: Cancel the proposed ownership transfer of a league
+    ///
+    /// Only the owner of the league by the name `league_name` may do this.
+    pub fn cancel_ownership_transfer(&mut self, league_name: String) {
+        let league = self.leagues.get(&league_name);
+        require!(league.is_some(), "League does not exist");
+        let mut league = league.unwrap();
+        let initial_storage = env::storage_usage();
+        require!(
+            league.caller_is_owner(),
+            "Only the owner may transfer the league"
+        );
+        league.cancel_ownership_transfer();
+        self.save_league(&league_name, &mut league, initial_storage);
+        env::log_str(&format!(
+            "{} cancelled the ownership transfer of league {}",
+            env::predecessor_account_id(),
+            league_name
+        ));
+    }
 
-        let mut contract = LeagueContract::new();
-        let players = vec!["Alice".to_string(), "Bob".to_string(), "Charly".to_string()];
-        let accs = vec![accounts(0), accounts(1)];
-        let name = "SomeLeague".to_string();
-        contract.create_league(name.clone(), players, accs, 3, StandardGameType);
-        contract.delete_league(name, true);
+    /// CALL: Accept the ownership of a league
+    ///
+    /// The caller has to be the proposed owner of the league by the name `league_name`.
+    /// Afterwards the caller is the owner with all its rights, e.g. deleting the league.
+    /// The pledge stays with the league and is refunded to the new owner.
+    pub fn accept_ownership(&mut self, league_name: String) {
+        let league = self.leagues.get(&league_name);
+        require!(league.is_some(), "League does not exist");
+        let mut league = league.unwrap();
+        let initial_storage = env::storage_usage();
+        league.accept_ownership();
+        self.save_league(&league_name, &mut league, initial_storage);
+        env::log_str(&format!(
+            "{} accepted the ownership of league {}",
+            env::predecessor_account_id(),
+            league_name
+        ));
     }
 
-    /// Test the panic of a unforced deletion of an unfinished league
-    #[test]
-    #[should_panic(expected = "League is not finished yet")]
-    fn test_delete_unfinished_league() {
-        let _context = create_context();
+    /// CALL: Add a trusted account to a league
+    ///
+    /// Only the owner of the league by the name `league_name` may do this.
+    /// The `account_id` may add games to the league afterwards.
+    pub fn add_trusted_account(&mut self, league_name: String, account_id: AccountId) {
+        let league = self.leagues.get(&league_name);
+        require!(league.is_some(), "League does not exist");
+        let mut league = league.unwrap();
+        let initial_storage = env::storage_usage();
+        require!(
+            league.caller_is_owner(),
+            "Only the owner may manage trusted accounts"
+        );
+        league.add_trusted_account(&account_id);
+        self.save_league(&league_name, &mut league, initial_storage);
+        env::log_str(&format!(
+            "{} added {} as trusted account to league {}",
+            env::predecessor_account_id(),
+            account_id,
+            league_name
+        ));
+    }
 
-        let mut contract = LeagueContract::new();
-        let players = vec!["Alice".to_string(), "Bob".to_string(), "Charly".to_string()];
-        let accs = vec![accounts(0), accounts(1)];
-        let name = "SomeLeague".to_string();
-        contract.create_league(name.clone(), players, accs, 3, StandardGameType);
-        contract.delete_league(name, false);
+    /// CALL: Remove a trusted account from a league
+    ///
+    /// Only the owner of the league by the name `league_name` may do this.
+    pub fn remove_trusted_account(&mut self, league_name: String, account_id: AccountId) {
+        let league = self.leagues.get(&league_name);
+        require!(league.is_some(), "League does not exist");
+        let mut league = league.unwrap();
+        let initial_storage = env::storage_usage();
+        require!(
+            league.caller_is_owner(),
+            "Only the owner may manage trusted accounts"
+        );
+        league.remove_trusted_account(&account_id);
+        self.save_league(&league_name, &mut league, initial_storage);
+        env::log_str(&format!(
+            "{} removed {} as trusted account from league {}",
+            env::predecessor_account_id(),
+            account_id,
+            league_name
+        ));
     }
 
-    /// Test rejection of deletion of a league from a not owner
-    #[test]
-    #[should_panic(expected = "You may not delete the league")]
-    fn test_foreigner_force_delete_unfinished_league() {
-        let mut context = create_context();
+    /// VIEW: Retrieve a list of all implemented game types
+    ///
+    /// Now the frontend (or other user) can check which types can be used to create a league
+    /// This could be actually static but does a static function make sense for a contract?!?
+    pub fn get_game_types(&self) -> Vec<&'static str> {
+        GameType::VARIANTS.to_vec()
+    }
+
+    /// VIEW: Retrieve the structure of a game type
+    ///
+    /// The frontend can build a form to add games to a league.
+    /// A JSON Schema of the game data will be returned
+    pub fn get_game_structure(&self, game_type: GameType) -> Value {
+        game_type.structure()
+    }
+
+    /// VIEW: Get the current league progress
+    ///
+    /// Retrieve the leagues progress with all matches and their games
+    pub fn get_league(&self, league_name: String) -> HelperLeague {
+        let league = self.leagues.get(&league_name);
+        require!(league.is_some(), "League does not exist");
+        league.unwrap().summarize_league()
+    }
+
+    /// VIEW: Get the trusted accounts of a league
+    ///
+    /// The owner is not part of the list but is trusted as well.
+    pub fn get_trusted_accounts(&self, league_name: String) -> Vec<AccountId> {
+        let league = self.leagues.get(&league_name);
+        require!(league.is_some(), "League does not exist");
+        league.unwrap().trusted_accounts()
+    }
+
+    /// VIEW: Get the pledge of a league
+    ///
+    /// Shows the deposit of the league and how much of it is bound by the used storage.
+    pub fn get_pledge(&self, league_name: String) -> HelperPledge {
+        let league = self.leagues.get(&league_name);
+        require!(league.is_some(), "League does not exist");
+        league.unwrap().summarize_pledge()
+    }
+
+    /// VIEW: Get the standings of a league
+    ///
+    /// The players are ordered by their points in the finished matches.
+    /// Ties are broken by the league's tiebreakers and finally by the order of the players.
+    /// With groups these are the group and the playoff matches together,
+    /// the tables of the group stage alone are given by `get_group_standings`.
+    pub fn get_standings(&self, league_name: String) -> Vec<StandingsEntry> {
+        let league = self.leagues.get(&league_name);
+        require!(league.is_some(), "League does not exist");
+        league.unwrap().standings()
+    }
+
+    /// VIEW: Get the bracket of a knockout league
+    ///
+    /// Every match of the bracket is listed round by round with its players as far as they are known.
+    /// A side can also be a bye or still pending on an earlier match.
+    pub fn get_bracket(&self, league_name: String) -> Vec<HelperBracketMatch> {
+        let league = self.leagues.get(&league_name);
+        require!(league.is_some(), "League does not exist");
+        let bracket = league.unwrap().summarize_bracket();
+        require!(
+            bracket.is_some(),
+            "League is not played in a knockout format"
+        );
+        bracket.unwrap()
+    }
+
+    /// VIEW: Get the standings of every group
+    ///
+    /// The groups are given in order, each with the standings of its group stage matches only.
+    pub fn get_group_standings(&self, league_name: String) -> Vec<Vec<StandingsEntry>> {
+        let league = self.leagues.get(&league_name);
+        require!(league.is_some(), "League does not exist");
+        let groups = league.unwrap().summarize_groups();
+        require!(groups.is_some(), "League is not played in groups");
+        groups.unwrap()
+    }
+
+    /// VIEW: Get the rounds of a swiss league
+    ///
+    /// Every paired round is listed with its pairings and the player with the bye.
+    /// The last round is the active one. The next round is paired as soon as it is finished.
+    pub fn get_swiss_rounds(&self, league_name: String) -> Vec<HelperSwissRound> {
+        let league = self.leagues.get(&league_name);
+        require!(league.is_some(), "League does not exist");
+        let rounds = league.unwrap().summarize_rounds();
+        require!(rounds.is_some(), "League is not played in the swiss format");
+        rounds.unwrap()
+    }
+
+    /// VIEW: Get the audit trail of the corrected games of a league
+    ///
+    /// Every correction is listed in order with the original game, who corrected it and when.
+    pub fn get_corrections(&self, league_name: String) -> Vec<HelperCorrection> {
+        let league = self.leagues.get(&league_name);
+        require!(league.is_some(), "League does not exist");
+        league.unwrap().summarize_corrections()
+    }
+
+    /// VIEW: Get the dispute log of a league
+    ///
+    /// Every dispute is listed by its id with the reason, the evidence, who opened it and when
+    /// and how it was resolved.
+    pub fn get_disputes(&self, league_name: String) -> Vec<HelperDispute> {
+        let league = self.leagues.get(&league_name);
+        require!(league.is_some(), "League does not exist");
+        league.unwrap().summarize_disputes()
+    }
+
+    /// VIEW: Get the matchdays of a round robin league
+    ///
+    /// Every matchday is listed with its pairings, the home player first, and the player with the bye.
+    /// The schedule is fixed from the start, so it also shows the matches which are not played yet.
+    pub fn get_schedule(&self, league_name: String) -> Vec<HelperMatchday> {
+        let league = self.leagues.get(&league_name);
+        require!(league.is_some(), "League does not exist");
+        let schedule = league.unwrap().summarize_schedule();
+        require!(schedule.is_some(), "League is not played as a round robin");
+        schedule.unwrap()
+    }
+}
+
+impl LeagueContract {
+    /// Change the lifecycle of the league by the name `league_name` with `change` and log it
+    fn change_lifecycle<F>(&mut self, league_name: &String, change: F)
+    where
+        F: FnOnce(&mut League),
+    {
+        let league = self.leagues.get(league_name);
+        require!(league.is_some(), "League does not exist");
+        let mut league = league.unwrap();
+        let initial_storage = env::storage_usage();
+        change(&mut league);
+        self.save_league(league_name, &mut league, initial_storage);
+        env::log_str(&format!(
+            "League {} is {:?} now",
+            league_name,
+            league.lifecycle()
+        ));
+    }
+
+    /// Save a changed league and account the storage difference to its pledge
+    ///
+    /// The pledge has to cover the used storage and the storage reserved for the open matches afterwards.
+    /// The accounting itself only changes fields of a fixed size, so saving again keeps the storage.
+    fn save_league(
+        &mut self,
+        league_name: &String,
+        league: &mut League,
+        initial_storage: StorageUsage,
+    ) {
+        self.leagues.insert(league_name, league);
+        league.account_storage(initial_storage);
+        require!(
+            league.pledge_covers(league.reserved_storage()),
+            "The pledge of the league does not cover its storage, please top up"
+        );
+        self.leagues.insert(league_name, league);
+    }
+}
+
+#[cfg(all(test, not(target_arch = "wasm32")))]
+mod tests {
+    use crate::game_module::GameOutcome;
+    use crate::game_module::GameOutcome::{Draw, FirstPlayerWon, SecondPlayerWon};
+    use crate::game_module::GameType;
+    use crate::game_module::GameType::{StandardGameType, StarcraftGameType};
+    use crate::main::bracket::BracketSection::{self, GrandFinal, Lower, Upper};
+    use crate::main::bracket::HelperOccupant::{self, Bye, Pending};
+    use crate::main::standings::{PointsScheme, Tiebreaker};
+    use crate::main::swiss;
+    use crate::main::DrawRule::{self, HalfPoint, Replay};
+    use crate::main::LeagueFormat::{
+        self, DoubleElimination, DoubleRoundRobin, GroupsAndPlayoffs, SingleElimination, Swiss,
+    };
+    use crate::main::MAX_PLAYERS;
+    use crate::main::{
+        BestOfOverride, CorrectionKind, DisputeRuling, ForfeitPolicy, ForfeitReason, LeagueOptions,
+        Lifecycle, Phase, Registration, Stage, Winner, WithdrawalPolicy, STORAGE_PER_GAME,
+        STORAGE_PER_MATCH,
+    };
+    use crate::main::{LeagueProperties, LeaguePropertiesV1, UpgradeableLeagueProperties};
+    use crate::LeagueContract;
+    use near_sdk::borsh::{self, BorshDeserialize};
+    use near_sdk::json_types::U64;
+    use near_sdk::test_utils::{accounts, get_logs, VMContextBuilder};
+    use near_sdk::{env, testing_env, Balance};
+    use serde_json::{json, Value};
+
+    /// The deposit attached to every call, more than enough as a pledge of a league
+    const DEPOSIT: Balance = 10_000_000_000_000_000_000_000_000;
+
+    fn create_context() -> VMContextBuilder {
+        let mut context = VMContextBuilder::new();
+        context
+            .current_account_id(accounts(0))
+            .signer_account_id(accounts(0))
+            .predecessor_account_id(accounts(0))
+            .attached_deposit(DEPOSIT);
+        testing_env!(context.build());
+        context
+    }
+
+    /// Test that checks if the default implementation panics as expected
+    #[test]
+    #[should_panic(expected = "The contract is not initialized")]
+    fn test_default() {
+        let _context = create_context();
+
+        let _contract = LeagueContract::default();
+    }
+
+    /// Test that checks that the init function functions fine.
+    #[test]
+    fn test_new() {
+        let _context = create_context();
+
+        let _contract = LeagueContract::new();
+    }
+
+    /// Test the creation of a league
+    #[test]
+    fn test_create_league() {
+        let _context = create_context();
+
+        let mut contract = LeagueContract::new();
+        let players = vec!["Alice".to_string(), "Bob".to_string(), "Charly".to_string()];
+        let accs = vec![accounts(0), accounts(1)];
+        contract.create_league(
+            "SomeLeague".to_string(),
+            players,
+            accs,
+            3,
+            StandardGameType,
+            None,
+        );
+    }
+
+    /// Test the expected panic of a league with more players than the indices can hold
+    #[test]
+    #[should_panic(expected = "League cannot have more than 128 players")]
+    fn test_create_league_too_many_players() {
+        let _context = create_context();
+
+        let mut contract = LeagueContract::new();
+        let players = (0..=MAX_PLAYERS as usize)
+            .map(|index| format!("Player {}", index))
+            .collect();
+        contract.create_league(
+            "SomeLeague".to_string(),
+            players,
+            Vec::new(),
+            1,
+            StandardGameType,
+            None,
+        );
+    }
+
+    /// Test the expected panic of a name collision in leagues
+    #[test]
+    #[should_panic(expected = "League with that name already exists")]
+    fn test_create_league_twice() {
+        let _context = create_context();
+
+        let mut contract = LeagueContract::new();
+        let players = vec!["Alice".to_string(), "Bob".to_string(), "Charly".to_string()];
+        let accs = vec![accounts(0), accounts(1)];
+        contract.create_league(
+            "SomeLeague".to_string(),
+            players.clone(),
+            accs.clone(),
+            3,
+            StandardGameType,
+            None,
+        );
+        contract.create_league(
+            "SomeLeague".to_string(),
+            players,
+            accs,
+            3,
+            StandardGameType,
+            None,
+        );
+    }
+
+    /// Test a forced deletion of a league
+    #[test]
+    fn test_force_delete_unfinished_league() {
+        let _context = create_context();
+
+        let mut contract = LeagueContract::new();
+        let players = vec!["Alice".to_string(), "Bob".to_string(), "Charly".to_string()];
+        let accs = vec![accounts(0), accounts(1)];
+        let name = "SomeLeague".to_string();
+        contract.create_league(name.clone(), players, accs, 3, StandardGameType, None);
+        contract.delete_league(name, true);
+    }
+
+    /// Test the panic of a unforced deletion of an unfinished league
+    #[test]
+    #[should_panic(expected = "League is not finished yet")]
+    fn test_delete_unfinished_league() {
+        let _context = create_context();
+
+        let mut contract = LeagueContract::new();
+        let players = vec!["Alice".to_string(), "Bob".to_string(), "Charly".to_string()];
+        let accs = vec![accounts(0), accounts(1)];
+        let name = "SomeLeague".to_string();
+        contract.create_league(name.clone(), players, accs, 3, StandardGameType, None);
+        contract.delete_league(name, false);
+    }
+
+    /// Test rejection of deletion of a league from a not owner
+    #[test]
+    #[should_panic(expected = "You may not delete the league")]
+    fn test_foreigner_force_delete_unfinished_league() {
+        let mut context = create_context();
 
         let mut contract = LeagueContract::new();
         let players = vec!["Alice".to_string(), "Bob".to_string(), "Charly".to_string()];
         let accs = vec![accounts(0), accounts(1)];
         let name = "SomeLeague".to_string();
-        contract.create_league(name.clone(), players, accs, 3, StandardGameType);
+        contract.create_league(name.clone(), players, accs, 3, StandardGameType, None);
 
         context.predecessor_account_id(accounts(1));
         testing_env!(context.build());
@@ -275,393 +1051,4848 @@ mod tests {
     }
 
     #[test]
-    fn test_add_games() {
+    fn test_transfer_ownership() {
         let mut context = create_context();
 
         let mut contract = LeagueContract::new();
         let name = "SomeLeague".to_string();
         let players = vec!["Alice".to_string(), "Bob".to_string(), "Charly".to_string()];
-        let accs = vec![accounts(1)];
-        contract.create_league(name.clone(), players.clone(), accs, 3, StandardGameType);
+        contract.create_league(
+            name.clone(),
+            players,
+            vec![accounts(1)],
+            3,
+            StandardGameType,
+            None,
+        );
+        contract.propose_owner(name.clone(), accounts(1));
+        assert_eq!(
+            Some(accounts(1)),
+            contract.get_league(name.clone()).proposed_owner
+        );
 
         context.predecessor_account_id(accounts(1));
         testing_env!(context.build());
+        contract.accept_ownership(name.clone());
 
-        contract.add_game(
-            name.clone(),
-            (players[0].clone(), players[1].clone()),
-            true,
-            "{}".to_string(),
-        );
-        contract.add_game(
-            name.clone(),
-            (players[0].clone(), players[1].clone()),
-            false,
-            "{}".to_string(),
-        );
-        contract.add_game(
-            name,
-            (players[0].clone(), players[1].clone()),
-            true,
-            "{}".to_string(),
-        );
-        // TODO add a view later to verify finished game
+        let summary = contract.get_league(name.clone());
+        assert_eq!(accounts(1), summary.owner);
+        assert_eq!(None, summary.proposed_owner);
+        assert!(contract.get_trusted_accounts(name.clone()).is_empty());
+
+        // The new owner has all the rights
+        contract.add_trusted_account(name.clone(), accounts(0));
+        contract.delete_league(name, true);
     }
 
     #[test]
-    #[should_panic(expected = "Game data cannot be parsed in the game type")]
-    fn test_add_wrong_game_data() {
+    #[should_panic(expected = "You may not delete the league")]
+    fn test_former_owner_deletes_league() {
         let mut context = create_context();
 
         let mut contract = LeagueContract::new();
         let name = "SomeLeague".to_string();
         let players = vec!["Alice".to_string(), "Bob".to_string(), "Charly".to_string()];
-        let accs = vec![accounts(1)];
-        contract.create_league(name.clone(), players.clone(), accs, 3, StandardGameType);
+        contract.create_league(name.clone(), players, Vec::new(), 3, StandardGameType, None);
+        contract.propose_owner(name.clone(), accounts(1));
 
         context.predecessor_account_id(accounts(1));
         testing_env!(context.build());
+        contract.accept_ownership(name.clone());
 
-        contract.add_game(
-            name,
-            (players[0].clone(), players[1].clone()),
-            true,
-            "{house: true}".to_string(),
-        );
+        context.predecessor_account_id(accounts(0));
+        testing_env!(context.build());
+        contract.delete_league(name, true);
     }
 
     #[test]
-    #[should_panic(expected = "Match is already finished")]
-    fn test_add_game_to_finished_match() {
+    #[should_panic(expected = "The ownership is not proposed to you")]
+    fn test_accept_cancelled_ownership() {
         let mut context = create_context();
 
         let mut contract = LeagueContract::new();
         let name = "SomeLeague".to_string();
         let players = vec!["Alice".to_string(), "Bob".to_string(), "Charly".to_string()];
-        let accs = vec![accounts(1)];
-        contract.create_league(name.clone(), players.clone(), accs, 3, StandardGameType);
+        contract.create_league(name.clone(), players, Vec::new(), 3, StandardGameType, None);
+        contract.propose_owner(name.clone(), accounts(1));
+        contract.cancel_ownership_transfer(name.clone());
 
         context.predecessor_account_id(accounts(1));
         testing_env!(context.build());
+        contract.accept_ownership(name);
+    }
 
-        contract.add_game(
+    #[test]
+    #[should_panic(expected = "The ownership is not proposed to you")]
+    fn test_accept_ownership_proposed_to_someone_else() {
+        let mut context = create_context();
+
+        let mut contract = LeagueContract::new();
+        let name = "SomeLeague".to_string();
+        let players = vec!["Alice".to_string(), "Bob".to_string(), "Charly".to_string()];
+        contract.create_league(name.clone(), players, Vec::new(), 3, StandardGameType, None);
+        contract.propose_owner(name.clone(), accounts(1));
+
+        context.predecessor_account_id(accounts(2));
+        testing_env!(context.build());
+        contract.accept_ownership(name);
+    }
+
+    #[test]
+    #[should_panic(expected = "Only the owner may transfer the league")]
+    fn test_trusted_account_proposes_owner() {
+        let mut context = create_context();
+
+        let mut contract = LeagueContract::new();
+        let name = "SomeLeague".to_string();
+        let players = vec!["Alice".to_string(), "Bob".to_string(), "Charly".to_string()];
+        contract.create_league(
             name.clone(),
-            (players[0].clone(), players[1].clone()),
-            true,
-            "{}".to_string(),
-        );
+            players,
+            vec![accounts(1)],
+            3,
+            StandardGameType,
+            None,
+        );
+
+        context.predecessor_account_id(accounts(1));
+        testing_env!(context.build());
+        contract.propose_owner(name, accounts(1));
+    }
+
+    #[test]
+    #[should_panic(expected = "Attached deposit does not cover the storage of the league")]
+    fn test_create_league_without_deposit() {
+        let mut context = create_context();
+        context.attached_deposit(0);
+        testing_env!(context.build());
+
+        let mut contract = LeagueContract::new();
+        let players = vec!["Alice".to_string(), "Bob".to_string(), "Charly".to_string()];
+        contract.create_league(
+            "SomeLeague".to_string(),
+            players,
+            Vec::new(),
+            3,
+            StandardGameType,
+            None,
+        );
+    }
+
+    /// Creates a Bo1 StarCraft league with a pledge just covering the league and its expected matches
+    ///
+    /// Another league with a name of the same length measures the storage of the league first.
+    fn create_minimal_pledge_league(context: &mut VMContextBuilder, contract: &mut LeagueContract) {
+        let players = vec!["Alice".to_string(), "Bob".to_string(), "Charly".to_string()];
+        contract.create_league(
+            "Measure".to_string(),
+            players.clone(),
+            Vec::new(),
+            1,
+            StarcraftGameType,
+            None,
+        );
+        let usage = contract.get_pledge("Measure".to_string()).storage_usage;
+        let reserved = 3 * (STORAGE_PER_MATCH + STORAGE_PER_GAME);
+        let minimal = (usage + reserved) as Balance * env::storage_byte_cost();
+
+        context.attached_deposit(minimal);
+        testing_env!(context.build());
+        contract.create_league(
+            "Minimal".to_string(),
+            players,
+            Vec::new(),
+            1,
+            StarcraftGameType,
+            None,
+        );
+        let pledge = contract.get_pledge("Minimal".to_string());
+        assert_eq!(minimal, pledge.pledge.0);
+        assert_eq!(usage, pledge.storage_usage);
+    }
+
+    /// A game with a long replay reference uses more storage than estimated
+    fn add_large_starcraft_game(contract: &mut LeagueContract, name: &str) {
+        let replay = "x".repeat(5000);
+        contract.add_game(
+            name.to_string(),
+            ("Alice".to_string(), "Bob".to_string()),
+            FirstPlayerWon,
+            json!({"first_player_race": "Zerg", "second_player_race": "Terran", "map": "Oxide LE", "duration": 754, "replay": replay}).to_string(),
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "The pledge of the league does not cover its storage, please top up")]
+    fn test_storage_exceeds_pledge() {
+        let mut context = create_context();
+
+        let mut contract = LeagueContract::new();
+        create_minimal_pledge_league(&mut context, &mut contract);
+        add_large_starcraft_game(&mut contract, "Minimal");
+    }
+
+    /// The storage reserved for the open matches cannot be used for anything else
+    #[test]
+    #[should_panic(expected = "The pledge of the league does not cover its storage, please top up")]
+    fn test_storage_reserved_for_matches() {
+        let mut context = create_context();
+
+        let mut contract = LeagueContract::new();
+        create_minimal_pledge_league(&mut context, &mut contract);
+        contract.add_trusted_account("Minimal".to_string(), accounts(1));
+    }
+
+    #[test]
+    fn test_top_up_pledge() {
+        let mut context = create_context();
+
+        let mut contract = LeagueContract::new();
+        create_minimal_pledge_league(&mut context, &mut contract);
+        let pledge = contract.get_pledge("Minimal".to_string()).pledge.0;
+
+        context.attached_deposit(DEPOSIT);
+        testing_env!(context.build());
+        contract.top_up_pledge("Minimal".to_string());
+        add_large_starcraft_game(&mut contract, "Minimal");
+
+        let after = contract.get_pledge("Minimal".to_string());
+        assert_eq!(pledge + DEPOSIT, after.pledge.0);
+        assert!(after.storage_usage > 5000);
+        assert_eq!(
+            after.storage_usage as Balance * env::storage_byte_cost(),
+            after.storage_cost.0
+        );
+    }
+
+    #[test]
+    fn test_refund_pledge() {
+        let _context = create_context();
+
+        let mut contract = LeagueContract::new();
+        let name = "SomeLeague".to_string();
+        let players = vec!["Alice".to_string(), "Bob".to_string(), "Charly".to_string()];
+        contract.create_league(name.clone(), players, Vec::new(), 3, StandardGameType, None);
+        play_match(
+            &mut contract,
+            "SomeLeague",
+            ("Alice", "Bob"),
+            &[FirstPlayerWon],
+        );
+
+        // All storage of the league is freed, so the whole pledge is refunded
+        contract.delete_league(name, true);
+        assert_eq!(
+            vec![format!(
+                "Refunded {} of the pledge of league SomeLeague to alice",
+                DEPOSIT
+            )],
+            get_logs()
+        );
+    }
+
+    #[test]
+    fn test_refund_pledge_to_new_owner() {
+        let mut context = create_context();
+
+        let mut contract = LeagueContract::new();
+        let name = "SomeLeague".to_string();
+        let players = vec!["Alice".to_string(), "Bob".to_string(), "Charly".to_string()];
+        contract.create_league(name.clone(), players, Vec::new(), 3, StandardGameType, None);
+        contract.propose_owner(name.clone(), accounts(1));
+
+        // A top up of somebody else goes to the owner as well
+        context.predecessor_account_id(accounts(2));
+        testing_env!(context.build());
+        contract.top_up_pledge(name.clone());
+
+        context.predecessor_account_id(accounts(1));
+        testing_env!(context.build());
+        contract.accept_ownership(name.clone());
+
+        let pledge = contract.get_pledge(name.clone());
+        assert_eq!(2 * DEPOSIT, pledge.pledge.0);
+        let initial_storage = env::storage_usage();
+        contract.delete_league(name, true);
+        let freed = initial_storage - env::storage_usage();
+        let remaining = (pledge.storage_usage - freed) as Balance * env::storage_byte_cost();
+        assert_eq!(
+            Some(&format!(
+                "Refunded {} of the pledge of league SomeLeague to bob",
+                2 * DEPOSIT - remaining
+            )),
+            get_logs().last()
+        );
+    }
+
+    #[test]
+    fn test_add_games() {
+        let mut context = create_context();
+
+        let mut contract = LeagueContract::new();
+        let name = "SomeLeague".to_string();
+        let players = vec!["Alice".to_string(), "Bob".to_string(), "Charly".to_string()];
+        let accs = vec![accounts(1)];
+        contract.create_league(
+            name.clone(),
+            players.clone(),
+            accs,
+            3,
+            StandardGameType,
+            None,
+        );
+
+        context.predecessor_account_id(accounts(1));
+        testing_env!(context.build());
+
         contract.add_game(
             name.clone(),
             (players[0].clone(), players[1].clone()),
-            true,
+            FirstPlayerWon,
+            "{}".to_string(),
+        );
+        contract.add_game(
+            name.clone(),
+            (players[0].clone(), players[1].clone()),
+            SecondPlayerWon,
+            "{}".to_string(),
+        );
+        contract.add_game(
+            name,
+            (players[0].clone(), players[1].clone()),
+            FirstPlayerWon,
+            "{}".to_string(),
+        );
+        // TODO add a view later to verify finished game
+    }
+
+    #[test]
+    #[should_panic(expected = "You may not add games to the league")]
+    fn test_untrusted_add_game() {
+        let mut context = create_context();
+
+        let mut contract = LeagueContract::new();
+        let name = "SomeLeague".to_string();
+        let players = vec!["Alice".to_string(), "Bob".to_string(), "Charly".to_string()];
+        contract.create_league(
+            name.clone(),
+            players.clone(),
+            vec![accounts(1)],
+            3,
+            StandardGameType,
+            None,
+        );
+
+        context.predecessor_account_id(accounts(2));
+        testing_env!(context.build());
+
+        contract.add_game(
+            name,
+            (players[0].clone(), players[1].clone()),
+            FirstPlayerWon,
             "{}".to_string(),
         );
+    }
+
+    #[test]
+    fn test_manage_trusted_accounts() {
+        let _context = create_context();
+
+        let mut contract = LeagueContract::new();
+        let name = "SomeLeague".to_string();
+        let players = vec!["Alice".to_string(), "Bob".to_string(), "Charly".to_string()];
+        contract.create_league(
+            name.clone(),
+            players,
+            vec![accounts(0), accounts(1)],
+            3,
+            StandardGameType,
+            None,
+        );
+        assert_eq!(
+            vec![accounts(1)],
+            contract.get_trusted_accounts(name.clone())
+        );
+
+        contract.add_trusted_account(name.clone(), accounts(2));
+        contract.remove_trusted_account(name.clone(), accounts(1));
+        assert_eq!(
+            vec![accounts(2)],
+            contract.get_trusted_accounts(name.clone())
+        );
+        assert_eq!(
+            vec![
+                "alice added charlie as trusted account to league SomeLeague".to_string(),
+                "alice removed bob as trusted account from league SomeLeague".to_string()
+            ],
+            get_logs()
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "Only the owner may manage trusted accounts")]
+    fn test_trusted_account_adds_trusted_account() {
+        let mut context = create_context();
+
+        let mut contract = LeagueContract::new();
+        let name = "SomeLeague".to_string();
+        let players = vec!["Alice".to_string(), "Bob".to_string(), "Charly".to_string()];
+        contract.create_league(
+            name.clone(),
+            players,
+            vec![accounts(1)],
+            3,
+            StandardGameType,
+            None,
+        );
+
+        context.predecessor_account_id(accounts(1));
+        testing_env!(context.build());
+
+        contract.add_trusted_account(name, accounts(2));
+    }
+
+    #[test]
+    #[should_panic(expected = "Account is not trusted")]
+    fn test_remove_unknown_trusted_account() {
+        let _context = create_context();
+
+        let mut contract = LeagueContract::new();
+        let name = "SomeLeague".to_string();
+        let players = vec!["Alice".to_string(), "Bob".to_string(), "Charly".to_string()];
+        contract.create_league(name.clone(), players, Vec::new(), 3, StandardGameType, None);
+        contract.remove_trusted_account(name, accounts(1));
+    }
+
+    #[test]
+    #[should_panic(expected = "You may not add games to the league")]
+    fn test_removed_trusted_account_adds_game() {
+        let mut context = create_context();
+
+        let mut contract = LeagueContract::new();
+        let name = "SomeLeague".to_string();
+        let players = vec!["Alice".to_string(), "Bob".to_string(), "Charly".to_string()];
+        contract.create_league(
+            name.clone(),
+            players.clone(),
+            vec![accounts(1)],
+            3,
+            StandardGameType,
+            None,
+        );
+        contract.remove_trusted_account(name.clone(), accounts(1));
+
+        context.predecessor_account_id(accounts(1));
+        testing_env!(context.build());
+
         contract.add_game(
             name,
             (players[0].clone(), players[1].clone()),
-            true,
+            FirstPlayerWon,
             "{}".to_string(),
         );
     }
 
     #[test]
-    #[should_panic(expected = "At least one player not found in the league")]
-    fn test_add_game_with_wrong_player() {
+    #[should_panic(expected = "Game data cannot be parsed in the game type")]
+    fn test_add_wrong_game_data() {
+        let mut context = create_context();
+
+        let mut contract = LeagueContract::new();
+        let name = "SomeLeague".to_string();
+        let players = vec!["Alice".to_string(), "Bob".to_string(), "Charly".to_string()];
+        let accs = vec![accounts(1)];
+        contract.create_league(
+            name.clone(),
+            players.clone(),
+            accs,
+            3,
+            StandardGameType,
+            None,
+        );
+
+        context.predecessor_account_id(accounts(1));
+        testing_env!(context.build());
+
+        contract.add_game(
+            name,
+            (players[0].clone(), players[1].clone()),
+            FirstPlayerWon,
+            "{house: true}".to_string(),
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "Match is already finished")]
+    fn test_add_game_to_finished_match() {
+        let mut context = create_context();
+
+        let mut contract = LeagueContract::new();
+        let name = "SomeLeague".to_string();
+        let players = vec!["Alice".to_string(), "Bob".to_string(), "Charly".to_string()];
+        let accs = vec![accounts(1)];
+        contract.create_league(
+            name.clone(),
+            players.clone(),
+            accs,
+            3,
+            StandardGameType,
+            None,
+        );
+
+        context.predecessor_account_id(accounts(1));
+        testing_env!(context.build());
+
+        contract.add_game(
+            name.clone(),
+            (players[0].clone(), players[1].clone()),
+            FirstPlayerWon,
+            "{}".to_string(),
+        );
+        contract.add_game(
+            name.clone(),
+            (players[0].clone(), players[1].clone()),
+            FirstPlayerWon,
+            "{}".to_string(),
+        );
+        contract.add_game(
+            name,
+            (players[0].clone(), players[1].clone()),
+            FirstPlayerWon,
+            "{}".to_string(),
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "At least one player not found in the league")]
+    fn test_add_game_with_wrong_player() {
+        let mut context = create_context();
+
+        let mut contract = LeagueContract::new();
+        let name = "SomeLeague".to_string();
+        let players = vec!["Alice".to_string(), "Bob".to_string(), "Charly".to_string()];
+        let accs = vec![accounts(1)];
+        contract.create_league(
+            name.clone(),
+            players.clone(),
+            accs,
+            3,
+            StandardGameType,
+            None,
+        );
+
+        context.predecessor_account_id(accounts(1));
+        testing_env!(context.build());
+
+        contract.add_game(
+            name,
+            ("Malory".to_string(), players[1].clone()),
+            FirstPlayerWon,
+            "{}".to_string(),
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "Need different players")]
+    fn test_add_game_with_player_twice() {
+        let mut context = create_context();
+
+        let mut contract = LeagueContract::new();
+        let name = "SomeLeague".to_string();
+        let players = vec!["Alice".to_string(), "Bob".to_string(), "Charly".to_string()];
+        let accs = vec![accounts(1)];
+        contract.create_league(
+            name.clone(),
+            players.clone(),
+            accs,
+            3,
+            StandardGameType,
+            None,
+        );
+
+        context.predecessor_account_id(accounts(1));
+        testing_env!(context.build());
+
+        contract.add_game(
+            name,
+            (players[1].clone(), players[1].clone()),
+            FirstPlayerWon,
+            "{}".to_string(),
+        );
+    }
+
+    #[test]
+    fn test_delete_finished_match() {
+        // To lazy to define a new predecessor here
+        let _context = create_context();
+
+        let mut contract = LeagueContract::new();
+        let name = "SomeLeague".to_string();
+        let players = vec!["Alice".to_string(), "Bob".to_string(), "Charly".to_string()];
+        contract.create_league(
+            name.clone(),
+            players.clone(),
+            Vec::new(),
+            3,
+            StandardGameType,
+            None,
+        );
+
+        contract.add_game(
+            name.clone(),
+            (players[0].clone(), players[1].clone()),
+            FirstPlayerWon,
+            "{}".to_string(),
+        );
+        contract.add_game(
+            name.clone(),
+            (players[0].clone(), players[1].clone()),
+            FirstPlayerWon,
+            "{}".to_string(),
+        );
+
+        contract.add_game(
+            name.clone(),
+            (players[0].clone(), players[2].clone()),
+            SecondPlayerWon,
+            "{}".to_string(),
+        );
+        contract.add_game(
+            name.clone(),
+            (players[0].clone(), players[2].clone()),
+            SecondPlayerWon,
+            "{}".to_string(),
+        );
+
+        contract.add_game(
+            name.clone(),
+            (players[1].clone(), players[2].clone()),
+            FirstPlayerWon,
+            "{}".to_string(),
+        );
+        contract.add_game(
+            name.clone(),
+            (players[1].clone(), players[2].clone()),
+            SecondPlayerWon,
+            "{}".to_string(),
+        );
+        contract.add_game(
+            name.clone(),
+            (players[1].clone(), players[2].clone()),
+            FirstPlayerWon,
+            "{}".to_string(),
+        );
+
+        // None forced of course
+        contract.delete_league(name, false);
+    }
+
+    /// Test that a league recreated under the name of a deleted one starts clean
+    #[test]
+    fn test_recreate_deleted_league() {
+        let _context = create_context();
+
+        let mut contract = LeagueContract::new();
+        let name = "SomeLeague".to_string();
+        let initial_storage = env::storage_usage();
+        let players = vec!["Alice".to_string(), "Bob".to_string(), "Charly".to_string()];
+        contract.create_league(
+            name.clone(),
+            players,
+            vec![accounts(1)],
+            3,
+            StandardGameType,
+            None,
+        );
+        play_match(
+            &mut contract,
+            "SomeLeague",
+            ("Alice", "Bob"),
+            &[FirstPlayerWon, FirstPlayerWon],
+        );
+        contract.delete_league(name.clone(), true);
+        assert_eq!(initial_storage, env::storage_usage());
+
+        let players = vec!["Dave".to_string(), "Eve".to_string(), "Frank".to_string()];
+        contract.create_league(
+            name.clone(),
+            players.clone(),
+            Vec::new(),
+            3,
+            StandardGameType,
+            None,
+        );
+
+        assert!(contract.get_league(name.clone()).matches.is_empty());
+        assert!(contract.get_trusted_accounts(name.clone()).is_empty());
+        let standings = contract.get_standings(name);
+        assert_eq!(
+            players,
+            standings
+                .iter()
+                .map(|entry| entry.player.clone())
+                .collect::<Vec<_>>()
+        );
+        assert!(standings.iter().all(|entry| entry.matches_played == 0));
+    }
+
+    #[test]
+    fn test_simpler_league() {
+        // To lazy to define a new predecessor here
+        let _context = create_context();
+
+        let mut contract = LeagueContract::new();
+        let name = "SomeLeague".to_string();
+        let players = vec!["Alice".to_string(), "Bob".to_string(), "Charly".to_string()];
+        contract.create_league(
+            name.clone(),
+            players.clone(),
+            Vec::new(),
+            1,
+            StandardGameType,
+            None,
+        );
+
+        contract.add_game(
+            name.clone(),
+            (players[0].clone(), players[1].clone()),
+            FirstPlayerWon,
+            "{}".to_string(),
+        );
+        contract.add_game(
+            name.clone(),
+            (players[0].clone(), players[2].clone()),
+            SecondPlayerWon,
+            "{}".to_string(),
+        );
+        contract.add_game(
+            name.clone(),
+            (players[1].clone(), players[2].clone()),
+            FirstPlayerWon,
+            "{}".to_string(),
+        );
+
+        // None forced of course
+        contract.delete_league(name, false);
+    }
+
+    #[test]
+    fn test_complex_league() {
+        // To lazy to define a new predecessor here
+        let _context = create_context();
+
+        let mut contract = LeagueContract::new();
+        let name = "SomeLeague".to_string();
+        let players = vec!["Alice".to_string(), "Bob".to_string(), "Charly".to_string()];
+        contract.create_league(
+            name.clone(),
+            players.clone(),
+            Vec::new(),
+            5,
+            StandardGameType,
+            None,
+        );
+
+        contract.add_game(
+            name.clone(),
+            (players[0].clone(), players[1].clone()),
+            FirstPlayerWon,
+            "{}".to_string(),
+        );
+        contract.add_game(
+            name.clone(),
+            (players[0].clone(), players[1].clone()),
+            SecondPlayerWon,
+            "{}".to_string(),
+        );
+        contract.add_game(
+            name.clone(),
+            (players[0].clone(), players[1].clone()),
+            SecondPlayerWon,
+            "{}".to_string(),
+        );
+        contract.add_game(
+            name.clone(),
+            (players[0].clone(), players[1].clone()),
+            FirstPlayerWon,
+            "{}".to_string(),
+        );
+        contract.add_game(
+            name,
+            (players[0].clone(), players[1].clone()),
+            FirstPlayerWon,
+            "{}".to_string(),
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "Match is already finished")]
+    fn test_complex_league_failing() {
+        // To lazy to define a new predecessor here
+        let _context = create_context();
+
+        let mut contract = LeagueContract::new();
+        let name = "SomeLeague".to_string();
+        let players = vec!["Alice".to_string(), "Bob".to_string(), "Charly".to_string()];
+        contract.create_league(
+            name.clone(),
+            players.clone(),
+            Vec::new(),
+            5,
+            StandardGameType,
+            None,
+        );
+
+        contract.add_game(
+            name.clone(),
+            (players[0].clone(), players[1].clone()),
+            FirstPlayerWon,
+            "{}".to_string(),
+        );
+        contract.add_game(
+            name.clone(),
+            (players[0].clone(), players[1].clone()),
+            SecondPlayerWon,
+            "{}".to_string(),
+        );
+        contract.add_game(
+            name.clone(),
+            (players[0].clone(), players[1].clone()),
+            SecondPlayerWon,
+            "{}".to_string(),
+        );
+        contract.add_game(
+            name.clone(),
+            (players[0].clone(), players[1].clone()),
+            FirstPlayerWon,
+            "{}".to_string(),
+        );
+        contract.add_game(
+            name.clone(),
+            (players[0].clone(), players[1].clone()),
+            FirstPlayerWon,
+            "{}".to_string(),
+        );
+        contract.add_game(
+            name,
+            (players[0].clone(), players[1].clone()),
+            SecondPlayerWon,
+            "{}".to_string(),
+        );
+    }
+
+    /// Plays the `outcomes` between Alice and Bob and returns the winner of their match
+    ///
+    /// The storage stays the same within a test, so each league needs its own `name`.
+    fn winner_after(
+        name: &str,
+        best_of: u8,
+        draw_rule: DrawRule,
+        outcomes: Vec<GameOutcome>,
+    ) -> Value {
+        let _context = create_context();
+
+        let mut contract = LeagueContract::new();
+        let name = name.to_string();
+        let players = vec!["Alice".to_string(), "Bob".to_string(), "Charly".to_string()];
+        contract.create_league(
+            name.clone(),
+            players.clone(),
+            Vec::new(),
+            best_of,
+            StandardGameType,
+            Some(LeagueOptions {
+                draw_rule: Some(draw_rule),
+                ..Default::default()
+            }),
+        );
+        for outcome in outcomes {
+            contract.add_game(
+                name.clone(),
+                (players[0].clone(), players[1].clone()),
+                outcome,
+                "{}".to_string(),
+            );
+        }
+        let summary = serde_json::to_value(contract.get_league(name)).unwrap();
+        summary["matches"][0]["winner"].clone()
+    }
+
+    #[test]
+    fn test_bo1_with_draws() {
+        assert_eq!(
+            json!("Draw"),
+            winner_after("League1", 1, HalfPoint, vec![Draw])
+        );
+        assert_eq!(
+            json!("Undecided"),
+            winner_after("League2", 1, Replay, vec![Draw])
+        );
+        assert_eq!(
+            json!("SecondPlayer"),
+            winner_after("League3", 1, Replay, vec![Draw, Draw, SecondPlayerWon])
+        );
+    }
+
+    #[test]
+    fn test_bo3_with_draws() {
+        assert_eq!(
+            json!("Undecided"),
+            winner_after("League4", 3, HalfPoint, vec![FirstPlayerWon, Draw])
+        );
+        assert_eq!(
+            json!("FirstPlayer"),
+            winner_after("League5", 3, HalfPoint, vec![FirstPlayerWon, Draw, Draw])
+        );
+        assert_eq!(
+            json!("Draw"),
+            winner_after(
+                "League6",
+                3,
+                HalfPoint,
+                vec![FirstPlayerWon, SecondPlayerWon, Draw]
+            )
+        );
+        assert_eq!(
+            json!("Draw"),
+            winner_after("League7", 3, HalfPoint, vec![Draw, Draw, Draw])
+        );
+        assert_eq!(
+            json!("Undecided"),
+            winner_after("League8", 3, Replay, vec![FirstPlayerWon, Draw, Draw])
+        );
+        assert_eq!(
+            json!("FirstPlayer"),
+            winner_after(
+                "League9",
+                3,
+                Replay,
+                vec![FirstPlayerWon, Draw, Draw, FirstPlayerWon]
+            )
+        );
+    }
+
+    #[test]
+    fn test_bo5_with_draws() {
+        assert_eq!(
+            json!("SecondPlayer"),
+            winner_after(
+                "League10",
+                5,
+                HalfPoint,
+                vec![Draw, SecondPlayerWon, SecondPlayerWon, Draw]
+            )
+        );
+        assert_eq!(
+            json!("Draw"),
+            winner_after(
+                "League11",
+                5,
+                HalfPoint,
+                vec![FirstPlayerWon, SecondPlayerWon, Draw, Draw, Draw]
+            )
+        );
+        assert_eq!(
+            json!("SecondPlayer"),
+            winner_after(
+                "League12",
+                5,
+                Replay,
+                vec![
+                    Draw,
+                    SecondPlayerWon,
+                    FirstPlayerWon,
+                    Draw,
+                    SecondPlayerWon,
+                    SecondPlayerWon
+                ]
+            )
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "Match is already finished")]
+    fn test_add_game_to_drawn_match() {
+        winner_after(
+            "League13",
+            3,
+            HalfPoint,
+            vec![Draw, Draw, Draw, FirstPlayerWon],
+        );
+    }
+
+    /// A league where every match ended in a draw is finished as well
+    #[test]
+    fn test_delete_drawn_league() {
+        let _context = create_context();
+
+        let mut contract = LeagueContract::new();
+        let name = "SomeLeague".to_string();
+        let players = vec!["Alice".to_string(), "Bob".to_string(), "Charly".to_string()];
+        contract.create_league(
+            name.clone(),
+            players.clone(),
+            Vec::new(),
+            1,
+            StandardGameType,
+            None,
+        );
+        for (a, b) in [(0, 1), (0, 2), (1, 2)] {
+            contract.add_game(
+                name.clone(),
+                (players[a].clone(), players[b].clone()),
+                Draw,
+                "{}".to_string(),
+            );
+        }
+        contract.delete_league(name, false);
+    }
+
+    /// Adds all `outcomes` as games between the two `players` without any game data
+    fn play_match(
+        contract: &mut LeagueContract,
+        name: &str,
+        players: (&str, &str),
+        outcomes: &[GameOutcome],
+    ) {
+        for outcome in outcomes {
+            contract.add_game(
+                name.to_string(),
+                (players.0.to_string(), players.1.to_string()),
+                *outcome,
+                "{}".to_string(),
+            );
+        }
+    }
+
+    /// Creates a league of Alice, Bob, Charly and Dave with the given options
+    fn create_four_player_league(
+        contract: &mut LeagueContract,
+        name: &str,
+        best_of: u8,
+        options: LeagueOptions,
+    ) {
+        let players = vec!["Alice", "Bob", "Charly", "Dave"];
+        contract.create_league(
+            name.to_string(),
+            players.into_iter().map(|p| p.to_string()).collect(),
+            Vec::new(),
+            best_of,
+            StandardGameType,
+            Some(options),
+        );
+    }
+
+    /// Returns the player names in the order of the standings
+    fn standings_order(contract: &LeagueContract, name: &str) -> Vec<String> {
+        contract
+            .get_standings(name.to_string())
+            .into_iter()
+            .map(|e| e.player)
+            .collect()
+    }
+
+    #[test]
+    fn test_standings_without_games() {
+        let _context = create_context();
+
+        let mut contract = LeagueContract::new();
+        create_four_player_league(&mut contract, "SomeLeague", 3, LeagueOptions::default());
+        assert_eq!(
+            vec!["Alice", "Bob", "Charly", "Dave"],
+            standings_order(&contract, "SomeLeague")
+        );
+    }
+
+    #[test]
+    fn test_standings() {
+        let _context = create_context();
+
+        let mut contract = LeagueContract::new();
+        create_four_player_league(&mut contract, "SomeLeague", 3, LeagueOptions::default());
+        play_match(
+            &mut contract,
+            "SomeLeague",
+            ("Charly", "Alice"),
+            &[FirstPlayerWon, FirstPlayerWon],
+        );
+        play_match(
+            &mut contract,
+            "SomeLeague",
+            ("Bob", "Dave"),
+            &[FirstPlayerWon, Draw, SecondPlayerWon],
+        );
+        // Not finished, so it does not count yet
+        play_match(
+            &mut contract,
+            "SomeLeague",
+            ("Alice", "Bob"),
+            &[FirstPlayerWon],
+        );
+
+        let standings =
+            serde_json::to_value(contract.get_standings("SomeLeague".to_string())).unwrap();
+        assert_eq!(
+            json!([
+                {"rank": 1, "player": "Charly", "matches_played": 1, "matches_won": 1, "matches_drawn": 0, "matches_lost": 0, "games_won": 2, "games_lost": 0, "game_difference": 2, "points": 3, "walkovers": 0, "forfeits": 0, "withdrawn": false},
+                {"rank": 2, "player": "Bob", "matches_played": 1, "matches_won": 0, "matches_drawn": 1, "matches_lost": 0, "games_won": 1, "games_lost": 1, "game_difference": 0, "points": 1, "walkovers": 0, "forfeits": 0, "withdrawn": false},
+                {"rank": 3, "player": "Dave", "matches_played": 1, "matches_won": 0, "matches_drawn": 1, "matches_lost": 0, "games_won": 1, "games_lost": 1, "game_difference": 0, "points": 1, "walkovers": 0, "forfeits": 0, "withdrawn": false},
+                {"rank": 4, "player": "Alice", "matches_played": 1, "matches_won": 0, "matches_drawn": 0, "matches_lost": 1, "games_won": 0, "games_lost": 2, "game_difference": -2, "points": 0, "walkovers": 0, "forfeits": 0, "withdrawn": false}
+            ]),
+            standings
+        );
+    }
+
+    #[test]
+    fn test_standings_with_map_score() {
+        let _context = create_context();
+
+        let mut contract = LeagueContract::new();
+        let options = LeagueOptions {
+            points: PointsScheme::MapScore {
+                win: vec![3, 2],
+                draw: 1,
+                loss: vec![0, 1],
+            },
+            ..Default::default()
+        };
+        create_four_player_league(&mut contract, "SomeLeague", 3, options);
+        play_match(
+            &mut contract,
+            "SomeLeague",
+            ("Alice", "Bob"),
+            &[FirstPlayerWon, SecondPlayerWon, FirstPlayerWon],
+        );
+        play_match(
+            &mut contract,
+            "SomeLeague",
+            ("Charly", "Dave"),
+            &[FirstPlayerWon, FirstPlayerWon],
+        );
+
+        let points = contract
+            .get_standings("SomeLeague".to_string())
+            .into_iter()
+            .map(|e| (e.player, e.points))
+            .collect::<Vec<(String, u32)>>();
+        assert_eq!(
+            vec![
+                ("Charly".to_string(), 3),
+                ("Alice".to_string(), 2),
+                ("Bob".to_string(), 1),
+                ("Dave".to_string(), 0)
+            ],
+            points
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "Points for a map score need at least one entry for win and loss")]
+    fn test_invalid_map_score() {
+        let _context = create_context();
+
+        let mut contract = LeagueContract::new();
+        let options = LeagueOptions {
+            points: PointsScheme::MapScore {
+                win: vec![],
+                draw: 1,
+                loss: vec![0],
+            },
+            ..Default::default()
+        };
+        create_four_player_league(&mut contract, "SomeLeague", 3, options);
+    }
+
+    /// Alice and Bob as well as Charly and Dave end up with equal points.
+    /// The head-to-head decides against the game difference.
+    fn play_tied_league(contract: &mut LeagueContract, name: &str) {
+        play_match(
+            contract,
+            name,
+            ("Alice", "Bob"),
+            &[FirstPlayerWon, SecondPlayerWon, FirstPlayerWon],
+        );
+        play_match(
+            contract,
+            name,
+            ("Bob", "Charly"),
+            &[FirstPlayerWon, FirstPlayerWon],
+        );
+        play_match(
+            contract,
+            name,
+            ("Bob", "Dave"),
+            &[FirstPlayerWon, FirstPlayerWon],
+        );
+        play_match(
+            contract,
+            name,
+            ("Alice", "Charly"),
+            &[SecondPlayerWon, SecondPlayerWon],
+        );
+        play_match(
+            contract,
+            name,
+            ("Alice", "Dave"),
+            &[FirstPlayerWon, SecondPlayerWon, FirstPlayerWon],
+        );
+        play_match(
+            contract,
+            name,
+            ("Dave", "Charly"),
+            &[FirstPlayerWon, FirstPlayerWon],
+        );
+    }
+
+    #[test]
+    fn test_standings_head_to_head() {
+        let _context = create_context();
+
+        let mut contract = LeagueContract::new();
+        create_four_player_league(&mut contract, "SomeLeague", 3, LeagueOptions::default());
+        play_tied_league(&mut contract, "SomeLeague");
+        assert_eq!(
+            vec!["Alice", "Bob", "Dave", "Charly"],
+            standings_order(&contract, "SomeLeague")
+        );
+    }
+
+    #[test]
+    fn test_standings_game_difference_first() {
+        let _context = create_context();
+
+        let mut contract = LeagueContract::new();
+        let options = LeagueOptions {
+            tiebreakers: Some(vec![Tiebreaker::GameDifference, Tiebreaker::HeadToHead]),
+            ..Default::default()
+        };
+        create_four_player_league(&mut contract, "SomeLeague", 3, options);
+        play_tied_league(&mut contract, "SomeLeague");
+        assert_eq!(
+            vec!["Bob", "Alice", "Dave", "Charly"],
+            standings_order(&contract, "SomeLeague")
+        );
+    }
+
+    /// In a circle of wins the head-to-head is equal, the game difference decides
+    #[test]
+    fn test_standings_circle() {
+        let _context = create_context();
+
+        let mut contract = LeagueContract::new();
+        create_four_player_league(&mut contract, "SomeLeague", 3, LeagueOptions::default());
+        let name = "SomeLeague";
+        play_match(
+            &mut contract,
+            name,
+            ("Alice", "Bob"),
+            &[FirstPlayerWon, FirstPlayerWon],
+        );
+        play_match(
+            &mut contract,
+            name,
+            ("Bob", "Charly"),
+            &[FirstPlayerWon, SecondPlayerWon, FirstPlayerWon],
+        );
+        play_match(
+            &mut contract,
+            name,
+            ("Charly", "Alice"),
+            &[FirstPlayerWon, SecondPlayerWon, FirstPlayerWon],
+        );
+        assert_eq!(
+            vec!["Alice", "Charly", "Bob", "Dave"],
+            standings_order(&contract, name)
+        );
+    }
+
+    /// The idea of the macro is to to it only once and not add the game types everywhere
+    /// but for the beginning it is nice to test whether the view works
+    /// Or well maybe nice to check here if every game is inside
+    #[test]
+    fn test_list_game_types() {
+        let _context = create_context();
+
+        let contract = LeagueContract::new();
+        assert_eq!(
+            vec!("StandardGameType", "StarcraftGameType"),
+            contract.get_game_types()
+        );
+    }
+
+    #[test]
+    fn test_standard_game_structure() {
+        let _context = create_context();
+
+        let contract = LeagueContract::new();
+        assert_eq!(
+            json!({
+                "$schema": "http://json-schema.org/draft-07/schema#",
+                "title": "StandardGameData",
+                "type": "object",
+                "properties": {},
+                "required": [],
+                "additionalProperties": false
+            }),
+            contract.get_game_structure(GameType::StandardGameType)
+        );
+    }
+
+    #[test]
+    fn test_starcraft_game_structure() {
+        let _context = create_context();
+
+        let contract = LeagueContract::new();
+        let structure = contract.get_game_structure(GameType::StarcraftGameType);
+        assert_eq!(json!("StarcraftGameData"), structure["title"]);
+        assert_eq!(
+            json!({"type": "integer", "minimum": 1, "maximum": 86400}),
+            structure["properties"]["duration"]
+        );
+        assert_eq!(
+            json!({"anyOf": [{"type": "string"}, {"type": "null"}]}),
+            structure["properties"]["replay"]
+        );
+        assert_eq!(
+            json!({
+                "oneOf": [
+                    {"enum": ["Terran", "Zerg", "Protoss"]},
+                    {
+                        "type": "object",
+                        "properties": {"Random": {"enum": ["Terran", "Zerg", "Protoss"]}},
+                        "required": ["Random"],
+                        "additionalProperties": false
+                    }
+                ]
+            }),
+            structure["properties"]["first_player_race"]
+        );
+        assert_eq!(
+            json!(["first_player_race", "second_player_race", "map", "duration"]),
+            structure["required"]
+        );
+    }
+
+    /// Validates a JSON value against the subset of JSON Schema the `GameSchema` derive produces
+    fn validate(schema: &Value, value: &Value) -> bool {
+        if let Some(alternatives) = schema.get("oneOf").and_then(Value::as_array) {
+            let valid = alternatives.iter().filter(|s| validate(s, value)).count();
+            return valid == 1;
+        }
+        if let Some(alternatives) = schema.get("anyOf").and_then(Value::as_array) {
+            return alternatives.iter().any(|s| validate(s, value));
+        }
+        if let Some(variants) = schema.get("enum").and_then(Value::as_array) {
+            return variants.contains(value);
+        }
+        match schema["type"].as_str() {
+            Some("null") => value.is_null(),
+            Some("boolean") => value.is_boolean(),
+            Some("string") => value.is_string(),
+            Some("integer") => match value
+                .as_i64()
+                .map(|v| v as i128)
+                .or(value.as_u64().map(|v| v as i128))
+            {
+                None => false,
+                // Integers always come with their range
+                Some(v) => {
+                    v >= schema["minimum"].as_i64().unwrap() as i128
+                        && v <= schema["maximum"].as_u64().unwrap() as i128
+                }
+            },
+            Some("object") => {
+                let object = match value.as_object() {
+                    None => return false,
+                    Some(o) => o,
+                };
+                let properties = schema["properties"].as_object().unwrap();
+                let required = schema["required"].as_array().unwrap();
+                required
+                    .iter()
+                    .all(|r| object.contains_key(r.as_str().unwrap()))
+                    && object.iter().all(|(k, v)| match properties.get(k) {
+                        None => schema["additionalProperties"] != json!(false),
+                        Some(s) => validate(s, v),
+                    })
+            }
+            _ => panic!("Unsupported schema {}", schema),
+        }
+    }
+
+    /// Every game type needs valid and invalid samples here.
+    /// The schema has to agree with the contract whether a sample can be added as a game.
+    #[test]
+    fn test_game_data_against_schema() {
+        let _context = create_context();
+
+        let contract = LeagueContract::new();
+        for name in contract.get_game_types() {
+            let (valid, invalid) = match name {
+                "StandardGameType" => (vec![json!({})], vec![json!({"house": true}), json!([])]),
+                "StarcraftGameType" => (
+                    vec![
+                        json!({"first_player_race": "Zerg", "second_player_race": {"Random": "Protoss"}, "map": "Oxide LE", "duration": 754, "replay": null}),
+                        json!({"first_player_race": "Terran", "second_player_race": "Terran", "map": "Oxide LE", "duration": 1, "replay": "ipfs://replay"}),
+                        json!({"first_player_race": "Protoss", "second_player_race": "Zerg", "map": "Oxide LE", "duration": 86400}),
+                    ],
+                    vec![
+                        json!({"first_player_race": "Random", "second_player_race": "Zerg", "map": "Oxide LE", "duration": 754}),
+                        json!({"first_player_race": {"Random": "Random"}, "second_player_race": "Zerg", "map": "Oxide LE", "duration": 754}),
+                        json!({"first_player_race": "Zerg", "second_player_race": "Zerg", "map": "Oxide LE", "duration": 0}),
+                        json!({"first_player_race": "Zerg", "second_player_race": "Zerg", "map": "Oxide LE", "duration": 86401}),
+                        json!({"first_player_race": "Zerg", "second_player_race": "Zerg", "duration": 754}),
+                        json!({"first_player_race": "Zerg", "second_player_race": "Zerg", "map": 3, "duration": 754}),
+                    ],
+                ),
+                _ => panic!("No samples for {}", name),
+            };
+            let game_type: GameType = serde_json::from_value(json!(name)).unwrap();
+            let schema = contract.get_game_structure(game_type.clone());
+            for sample in valid {
+                assert!(validate(&schema, &sample), "{} rejects {}", name, sample);
+                assert!(game_type.convert(&sample.to_string(), false).is_some());
+            }
+            for sample in invalid {
+                assert!(!validate(&schema, &sample), "{} accepts {}", name, sample);
+                assert!(game_type.convert(&sample.to_string(), false).is_none());
+            }
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "Game data cannot be parsed in the game type")]
+    fn test_add_incomplete_starcraft_game() {
+        let _context = create_context();
+
+        let mut contract = LeagueContract::new();
+        let name = "SomeLeague".to_string();
+        let players = vec!["Alice".to_string(), "Bob".to_string(), "Charly".to_string()];
+        contract.create_league(
+            name.clone(),
+            players.clone(),
+            Vec::new(),
+            3,
+            StarcraftGameType,
+            None,
+        );
+
+        contract.add_game(
+            name,
+            (players[0].clone(), players[1].clone()),
+            FirstPlayerWon,
+            "{\"map\":\"Oxide LE\"}".to_string(),
+        );
+    }
+
+    /// The races have to follow the players if the names are given in the other order
+    #[test]
+    fn test_starcraft_league_output() {
+        let _context = create_context();
+
+        let mut contract = LeagueContract::new();
+        let name = "SomeLeague".to_string();
+        let players = vec!["Alice".to_string(), "Bob".to_string(), "Charly".to_string()];
+        contract.create_league(
+            name.clone(),
+            players.clone(),
+            Vec::new(),
+            3,
+            StarcraftGameType,
+            None,
+        );
+
+        contract.add_game(
+            name.clone(),
+            (players[1].clone(), players[0].clone()),
+            FirstPlayerWon,
+            "{\"first_player_race\":{\"Random\":\"Zerg\"},\"second_player_race\":\"Terran\",\"map\":\"Oxide LE\",\"duration\":754,\"replay\":null}".to_string(),
+        );
+
+        let summary = contract.get_league(name);
+        assert_eq!(
+            json!({
+                "owner": "alice",
+                "proposed_owner": null,
+                "format": "RoundRobin",
+                "lifecycle": "Running",
+                "registration": null,
+                "phase": "Regular",
+                "best_of": 3,
+                "draw_rule": "HalfPoint",
+                "players": ["Alice", "Bob", "Charly"],
+                "withdrawn": [],
+                "linked_accounts": [],
+                "link_requests": [],
+                "matches": [{
+                    "first_player": "Alice",
+                    "second_player": "Bob",
+                    "series": 0,
+                    "leg": null,
+                    "best_of": 3,
+                    "winner": "Undecided",
+                    "forfeit": null,
+                    "annulled": false,
+                    "pending": null,
+                    "games": [{
+                        "outcome": "SecondPlayerWon",
+                        "data": {
+                            "first_player_race": "Terran",
+                            "second_player_race": {"Random": "Zerg"},
+                            "map": "Oxide LE",
+                            "duration": 754,
+                            "replay": null
+                        }
+                    }]
+                }]
+            }),
+            serde_json::to_value(&summary).unwrap()
+        );
+    }
+
+    /// Kept it simple as the json gets quite annoying
+    #[test]
+    fn test_league_output() {
+        let _context = create_context();
+
+        let mut contract = LeagueContract::new();
+        let name = "SomeLeague".to_string();
+        let players = vec!["Alice".to_string(), "Bob".to_string(), "Charly".to_string()];
+        contract.create_league(
+            name.clone(),
+            players.clone(),
+            Vec::new(),
+            5,
+            StandardGameType,
+            None,
+        );
+
+        contract.add_game(
+            name,
+            (players[0].clone(), players[1].clone()),
+            FirstPlayerWon,
+            "{}".to_string(),
+        );
+
+        let summary = contract.get_league("SomeLeague".to_string());
+        let summary = serde_json::to_string(&summary).unwrap();
+        assert_eq!("{\"owner\":\"alice\",\"proposed_owner\":null,\"format\":\"RoundRobin\",\"lifecycle\":\"Running\",\"registration\":null,\"phase\":\"Regular\",\"best_of\":5,\"draw_rule\":\"HalfPoint\",\"players\":[\"Alice\",\"Bob\",\"Charly\"],\"withdrawn\":[],\"linked_accounts\":[],\"link_requests\":[],\"matches\":[{\"first_player\":\"Alice\",\"second_player\":\"Bob\",\"series\":0,\"leg\":null,\"best_of\":5,\"winner\":\"Undecided\",\"forfeit\":null,\"annulled\":false,\"pending\":null,\"games\":[{\"outcome\":\"FirstPlayerWon\",\"data\":{}}]}]}".to_string(), summary);
+        println!("{}", summary);
+    }
+
+    /// Test that the properties and games stored before drawn games are still read correctly
+    #[test]
+    fn test_read_v1_properties() {
+        let old = UpgradeableLeagueProperties::V1(LeaguePropertiesV1 {
+            best_of: 3,
+            game_type: StandardGameType,
+        });
+        let bytes = borsh::to_vec(&old).unwrap();
+        let prop = UpgradeableLeagueProperties::try_from_slice(&bytes).unwrap();
+        assert_eq!(3, prop.get_best_of());
+        assert!(prop.get_draw_rule() == Replay);
+
+        let upgraded = LeagueProperties::from(LeaguePropertiesV1 {
+            best_of: 5,
+            game_type: StandardGameType,
+        });
+        assert_eq!(5, upgraded.best_of);
+        assert!(upgraded.draw_rule == Replay);
+
+        // The byte of the former `first_player_is_winner` flag
+        assert_eq!(FirstPlayerWon, GameOutcome::try_from_slice(&[1]).unwrap());
+        assert_eq!(SecondPlayerWon, GameOutcome::try_from_slice(&[0]).unwrap());
+    }
+
+    /// Creates a single elimination cup with the given players in seed order
+    fn create_cup(contract: &mut LeagueContract, name: &str, players: &[&str]) {
+        contract.create_league(
+            name.to_string(),
+            players.iter().map(|p| p.to_string()).collect(),
+            Vec::new(),
+            1,
+            StandardGameType,
+            Some(LeagueOptions {
+                format: SingleElimination,
+                ..Default::default()
+            }),
+        );
+    }
+
+    fn player(name: &str) -> HelperOccupant {
+        HelperOccupant::Player(name.to_string())
+    }
+
+    /// Returns the sides and the winner of every bracket match
+    fn bracket_of(
+        contract: &LeagueContract,
+        name: &str,
+    ) -> Vec<(
+        BracketSection,
+        u8,
+        HelperOccupant,
+        HelperOccupant,
+        HelperOccupant,
+    )> {
+        contract
+            .get_bracket(name.to_string())
+            .into_iter()
+            .map(|m| (m.section, m.round, m.first, m.second, m.winner))
+            .collect()
+    }
+
+    #[test]
+    fn test_single_elimination_byes() {
+        let _context = create_context();
+
+        let mut contract = LeagueContract::new();
+        create_cup(
+            &mut contract,
+            "Cup",
+            &["Alice", "Bob", "Charly", "Dave", "Eve"],
+        );
+
+        // Eight slots, the top three seeds get a bye
+        assert_eq!(
+            vec![
+                (Upper, 1, player("Alice"), Bye, player("Alice")),
+                (Upper, 1, player("Dave"), player("Eve"), Pending),
+                (Upper, 1, player("Bob"), Bye, player("Bob")),
+                (Upper, 1, player("Charly"), Bye, player("Charly")),
+                (Upper, 2, player("Alice"), Pending, Pending),
+                (Upper, 2, player("Bob"), player("Charly"), Pending),
+                (Upper, 3, Pending, Pending, Pending),
+            ],
+            bracket_of(&contract, "Cup")
+        );
+
+        play_match(&mut contract, "Cup", ("Eve", "Dave"), &[FirstPlayerWon]);
+        play_match(&mut contract, "Cup", ("Bob", "Charly"), &[SecondPlayerWon]);
+        play_match(&mut contract, "Cup", ("Alice", "Eve"), &[FirstPlayerWon]);
+        assert_eq!(
+            (Upper, 3, player("Alice"), player("Charly"), Pending),
+            bracket_of(&contract, "Cup").pop().unwrap()
+        );
+
+        play_match(&mut contract, "Cup", ("Charly", "Alice"), &[FirstPlayerWon]);
+        assert_eq!(
+            (
+                Upper,
+                3,
+                player("Alice"),
+                player("Charly"),
+                player("Charly")
+            ),
+            bracket_of(&contract, "Cup").pop().unwrap()
+        );
+
+        // Four matches were played, so the cup is finished
+        contract.delete_league("Cup".to_string(), false);
+    }
+
+    #[test]
+    fn test_single_elimination_best_of() {
+        let _context = create_context();
+
+        let mut contract = LeagueContract::new();
+        create_four_player_league(
+            &mut contract,
+            "Cup",
+            3,
+            LeagueOptions {
+                format: SingleElimination,
+                ..Default::default()
+            },
+        );
+
+        // Draws are replayed, so the winner only advances after two won games
+        play_match(
+            &mut contract,
+            "Cup",
+            ("Alice", "Dave"),
+            &[FirstPlayerWon, Draw, SecondPlayerWon],
+        );
+        assert_eq!(
+            (Upper, 2, Pending, Pending, Pending),
+            bracket_of(&contract, "Cup").pop().unwrap()
+        );
+        play_match(&mut contract, "Cup", ("Alice", "Dave"), &[FirstPlayerWon]);
+        assert_eq!(
+            (Upper, 2, player("Alice"), Pending, Pending),
+            bracket_of(&contract, "Cup").pop().unwrap()
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "Players do not face each other in the current bracket")]
+    fn test_single_elimination_wrong_pairing() {
+        let _context = create_context();
+
+        let mut contract = LeagueContract::new();
+        create_cup(&mut contract, "Cup", &["Alice", "Bob", "Charly", "Dave"]);
+        play_match(&mut contract, "Cup", ("Alice", "Bob"), &[FirstPlayerWon]);
+    }
+
+    #[test]
+    #[should_panic(expected = "Players do not face each other in the current bracket")]
+    fn test_single_elimination_eliminated_player() {
+        let _context = create_context();
+
+        let mut contract = LeagueContract::new();
+        create_cup(&mut contract, "Cup", &["Alice", "Bob", "Charly", "Dave"]);
+        play_match(&mut contract, "Cup", ("Alice", "Dave"), &[SecondPlayerWon]);
+        play_match(&mut contract, "Cup", ("Bob", "Charly"), &[FirstPlayerWon]);
+        play_match(&mut contract, "Cup", ("Alice", "Bob"), &[FirstPlayerWon]);
+    }
+
+    #[test]
+    #[should_panic(expected = "League is not finished yet")]
+    fn test_single_elimination_unfinished() {
+        let _context = create_context();
+
+        let mut contract = LeagueContract::new();
+        create_cup(&mut contract, "Cup", &["Alice", "Bob", "Charly", "Dave"]);
+        play_match(&mut contract, "Cup", ("Alice", "Dave"), &[SecondPlayerWon]);
+        play_match(&mut contract, "Cup", ("Bob", "Charly"), &[FirstPlayerWon]);
+        contract.delete_league("Cup".to_string(), false);
+    }
+
+    #[test]
+    #[should_panic(expected = "Knockout formats need the Replay draw rule")]
+    fn test_single_elimination_half_point() {
+        let _context = create_context();
+
+        let mut contract = LeagueContract::new();
+        create_four_player_league(
+            &mut contract,
+            "Cup",
+            1,
+            LeagueOptions {
+                draw_rule: Some(HalfPoint),
+                format: SingleElimination,
+                ..Default::default()
+            },
+        );
+    }
+
+    #[test]
+    fn test_single_elimination_replays_draws() {
+        let _context = create_context();
+
+        let mut contract = LeagueContract::new();
+        create_cup(&mut contract, "Cup", &["Alice", "Bob", "Charly", "Dave"]);
+        assert!(matches!(
+            contract.get_league("Cup".to_string()).draw_rule,
+            Replay
+        ));
+    }
+
+    #[test]
+    #[should_panic(expected = "League is not played in a knockout format")]
+    fn test_round_robin_has_no_bracket() {
+        let _context = create_context();
+
+        let mut contract = LeagueContract::new();
+        create_four_player_league(&mut contract, "League", 1, LeagueOptions::default());
+        contract.get_bracket("League".to_string());
+    }
+
+    /// Creates a double elimination cup of Alice, Bob, Charly and Dave
+    ///
+    /// The upper bracket starts with Alice vs Dave and Bob vs Charly.
+    fn create_double_elimination_cup(
+        contract: &mut LeagueContract,
+        name: &str,
+        bracket_reset: bool,
+    ) {
+        create_four_player_league(
+            contract,
+            name,
+            1,
+            LeagueOptions {
+                format: DoubleElimination { bracket_reset },
+                ..Default::default()
+            },
+        );
+    }
+
+    /// Plays the cup up to the grand final between Alice and Bob
+    ///
+    /// Bob loses the upper final against Alice and meets Alice again after winning the lower bracket.
+    fn play_to_grand_final(contract: &mut LeagueContract, name: &str) {
+        play_match(contract, name, ("Alice", "Dave"), &[FirstPlayerWon]);
+        play_match(contract, name, ("Bob", "Charly"), &[FirstPlayerWon]);
+        play_match(contract, name, ("Dave", "Charly"), &[FirstPlayerWon]);
+        play_match(contract, name, ("Alice", "Bob"), &[FirstPlayerWon]);
+        play_match(contract, name, ("Dave", "Bob"), &[SecondPlayerWon]);
+    }
+
+    #[test]
+    fn test_double_elimination_bracket() {
+        let _context = create_context();
+
+        let mut contract = LeagueContract::new();
+        create_double_elimination_cup(&mut contract, "Cup", false);
+        play_to_grand_final(&mut contract, "Cup");
+
+        assert_eq!(
+            vec![
+                (Upper, 1, player("Alice"), player("Dave"), player("Alice")),
+                (Upper, 1, player("Bob"), player("Charly"), player("Bob")),
+                (Upper, 2, player("Alice"), player("Bob"), player("Alice")),
+                (Lower, 1, player("Dave"), player("Charly"), player("Dave")),
+                (Lower, 2, player("Dave"), player("Bob"), player("Bob")),
+                (GrandFinal, 1, player("Alice"), player("Bob"), Pending),
+            ],
+            bracket_of(&contract, "Cup")
+        );
+
+        // Without a reset the grand final decides the cup
+        play_match(&mut contract, "Cup", ("Bob", "Alice"), &[FirstPlayerWon]);
+        assert_eq!(
+            (GrandFinal, 1, player("Alice"), player("Bob"), player("Bob")),
+            bracket_of(&contract, "Cup").pop().unwrap()
+        );
+
+        // Both meetings of Alice and Bob are kept apart
+        let series = contract
+            .get_league("Cup".to_string())
+            .matches
+            .into_iter()
+            .filter(|m| m.first_player == "Alice" && m.second_player == "Bob")
+            .map(|m| (m.series, m.winner))
+            .collect::<Vec<_>>();
+        assert_eq!(2, series.len());
+        assert!(series
+            .iter()
+            .any(|(s, w)| *s == 0 && matches!(w, Winner::FirstPlayer)));
+        assert!(series
+            .iter()
+            .any(|(s, w)| *s == 1 && matches!(w, Winner::SecondPlayer)));
+
+        contract.delete_league("Cup".to_string(), false);
+    }
+
+    #[test]
+    fn test_double_elimination_bracket_reset() {
+        let _context = create_context();
+
+        let mut contract = LeagueContract::new();
+        create_double_elimination_cup(&mut contract, "Cup", true);
+        play_to_grand_final(&mut contract, "Cup");
+
+        // Bob wins the grand final, so Alice gets a second life in the reset
+        play_match(&mut contract, "Cup", ("Alice", "Bob"), &[SecondPlayerWon]);
+        assert_eq!(
+            (GrandFinal, 2, player("Bob"), player("Alice"), Pending),
+            bracket_of(&contract, "Cup").pop().unwrap()
+        );
+
+        play_match(&mut contract, "Cup", ("Alice", "Bob"), &[FirstPlayerWon]);
+        assert_eq!(
+            (
+                GrandFinal,
+                2,
+                player("Bob"),
+                player("Alice"),
+                player("Alice")
+            ),
+            bracket_of(&contract, "Cup").pop().unwrap()
+        );
+        contract.delete_league("Cup".to_string(), false);
+    }
+
+    #[test]
+    fn test_double_elimination_no_reset_needed() {
+        let _context = create_context();
+
+        let mut contract = LeagueContract::new();
+        create_double_elimination_cup(&mut contract, "Cup", true);
+        play_to_grand_final(&mut contract, "Cup");
+
+        // The upper champion wins the grand final, the reset is skipped
+        play_match(&mut contract, "Cup", ("Alice", "Bob"), &[FirstPlayerWon]);
+        assert_eq!(
+            (GrandFinal, 2, player("Alice"), Bye, player("Alice")),
+            bracket_of(&contract, "Cup").pop().unwrap()
+        );
+        contract.delete_league("Cup".to_string(), false);
+    }
+
+    #[test]
+    fn test_double_elimination_byes() {
+        let _context = create_context();
+
+        let mut contract = LeagueContract::new();
+        contract.create_league(
+            "Cup".to_string(),
+            vec!["Alice".to_string(), "Bob".to_string(), "Charly".to_string()],
+            Vec::new(),
+            1,
+            StandardGameType,
+            Some(LeagueOptions {
+                format: DoubleElimination {
+                    bracket_reset: false,
+                },
+                ..Default::default()
+            }),
+        );
+
+        // The bye of Alice drops down as well, so the loser of Bob and Charly advances
+        play_match(&mut contract, "Cup", ("Bob", "Charly"), &[FirstPlayerWon]);
+        assert_eq!(
+            (Lower, 1, Bye, player("Charly"), player("Charly")),
+            bracket_of(&contract, "Cup")[3]
+        );
+        play_match(&mut contract, "Cup", ("Alice", "Bob"), &[FirstPlayerWon]);
+        play_match(&mut contract, "Cup", ("Charly", "Bob"), &[FirstPlayerWon]);
+        play_match(&mut contract, "Cup", ("Alice", "Charly"), &[FirstPlayerWon]);
+        contract.delete_league("Cup".to_string(), false);
+    }
+
+    #[test]
+    #[should_panic(expected = "Players do not face each other in the current bracket")]
+    fn test_double_elimination_no_early_rematch() {
+        let _context = create_context();
+
+        let mut contract = LeagueContract::new();
+        create_double_elimination_cup(&mut contract, "Cup", false);
+        play_match(&mut contract, "Cup", ("Alice", "Dave"), &[FirstPlayerWon]);
+        play_match(&mut contract, "Cup", ("Alice", "Dave"), &[FirstPlayerWon]);
+    }
+
+    /// Creates a Bo1 swiss league with the given players in seed order
+    fn create_swiss_league(
+        contract: &mut LeagueContract,
+        name: &str,
+        players: &[&str],
+        rounds: u8,
+    ) {
+        contract.create_league(
+            name.to_string(),
+            players.iter().map(|p| p.to_string()).collect(),
+            Vec::new(),
+            1,
+            StandardGameType,
+            Some(LeagueOptions {
+                format: Swiss { rounds },
+                ..Default::default()
+            }),
+        );
+    }
+
+    /// The pairings of a round and the bye
+    type Round = (Vec<(String, String)>, Option<String>);
+
+    /// Returns the pairings of every round and the bye
+    fn swiss_rounds(contract: &LeagueContract, name: &str) -> Vec<Round> {
+        contract
+            .get_swiss_rounds(name.to_string())
+            .into_iter()
+            .map(|round| (round.pairings, round.bye))
+            .collect()
+    }
+
+    fn pairing(a: &str, b: &str) -> (String, String) {
+        (a.to_string(), b.to_string())
+    }
+
+    #[test]
+    fn test_swiss_pairings() {
+        let _context = create_context();
+
+        let mut contract = LeagueContract::new();
+        let players = ["Alice", "Bob", "Charly", "Dave", "Eve", "Frank"];
+        create_swiss_league(&mut contract, "Swiss", &players, 3);
+
+        // The top half plays against the bottom half in the first round
+        assert_eq!(
+            vec![(
+                vec![
+                    pairing("Alice", "Dave"),
+                    pairing("Bob", "Eve"),
+                    pairing("Charly", "Frank")
+                ],
+                None
+            )],
+            swiss_rounds(&contract, "Swiss")
+        );
+        play_match(&mut contract, "Swiss", ("Alice", "Dave"), &[FirstPlayerWon]);
+        play_match(&mut contract, "Swiss", ("Bob", "Eve"), &[FirstPlayerWon]);
+        assert_eq!(1, swiss_rounds(&contract, "Swiss").len());
+        play_match(
+            &mut contract,
+            "Swiss",
+            ("Charly", "Frank"),
+            &[SecondPlayerWon],
+        );
+
+        // Frank would face Charly again, so Dave is next
+        assert_eq!(
+            (
+                vec![
+                    pairing("Alice", "Bob"),
+                    pairing("Frank", "Dave"),
+                    pairing("Charly", "Eve")
+                ],
+                None
+            ),
+            swiss_rounds(&contract, "Swiss")[1]
+        );
+        play_match(&mut contract, "Swiss", ("Alice", "Bob"), &[FirstPlayerWon]);
+        play_match(&mut contract, "Swiss", ("Frank", "Dave"), &[FirstPlayerWon]);
+        play_match(&mut contract, "Swiss", ("Charly", "Eve"), &[Draw]);
+
+        // Alice and Charly had the stronger opponents than Frank and Eve
+        assert_eq!(
+            vec!["Alice", "Frank", "Bob", "Charly", "Eve", "Dave"],
+            standings_order(&contract, "Swiss")
+        );
+        assert_eq!(
+            (
+                vec![
+                    pairing("Alice", "Frank"),
+                    pairing("Bob", "Charly"),
+                    pairing("Eve", "Dave")
+                ],
+                None
+            ),
+            swiss_rounds(&contract, "Swiss")[2]
+        );
+        play_match(
+            &mut contract,
+            "Swiss",
+            ("Alice", "Frank"),
+            &[FirstPlayerWon],
+        );
+        play_match(&mut contract, "Swiss", ("Bob", "Charly"), &[FirstPlayerWon]);
+        play_match(&mut contract, "Swiss", ("Eve", "Dave"), &[FirstPlayerWon]);
+
+        // No further round after the last one
+        assert_eq!(3, swiss_rounds(&contract, "Swiss").len());
+        contract.delete_league("Swiss".to_string(), false);
+    }
+
+    #[test]
+    fn test_swiss_bye() {
+        let _context = create_context();
+
+        let mut contract = LeagueContract::new();
+        let players = ["Alice", "Bob", "Charly", "Dave", "Eve"];
+        create_swiss_league(&mut contract, "Swiss", &players, 2);
+
+        assert_eq!(
+            vec![(
+                vec![pairing("Alice", "Charly"), pairing("Bob", "Dave")],
+                Some("Eve".to_string())
+            )],
+            swiss_rounds(&contract, "Swiss")
+        );
+        // The bye counts as a won match
+        let eve = contract
+            .get_standings("Swiss".to_string())
+            .into_iter()
+            .find(|entry| entry.player == "Eve")
+            .unwrap();
+        assert_eq!((1, 1, 3), (eve.matches_played, eve.matches_won, eve.points));
+
+        play_match(
+            &mut contract,
+            "Swiss",
+            ("Alice", "Charly"),
+            &[FirstPlayerWon],
+        );
+        play_match(&mut contract, "Swiss", ("Bob", "Dave"), &[FirstPlayerWon]);
+
+        // Eve already had a bye, so the lowest ranked Dave gets the next one
+        assert_eq!(
+            (
+                vec![pairing("Alice", "Bob"), pairing("Eve", "Charly")],
+                Some("Dave".to_string())
+            ),
+            swiss_rounds(&contract, "Swiss")[1]
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "Players are not paired in the active round")]
+    fn test_swiss_unpaired_players() {
+        let _context = create_context();
+
+        let mut contract = LeagueContract::new();
+        let players = ["Alice", "Bob", "Charly", "Dave"];
+        create_swiss_league(&mut contract, "Swiss", &players, 2);
+        play_match(&mut contract, "Swiss", ("Alice", "Bob"), &[FirstPlayerWon]);
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "A swiss league needs at least one round and at most one round per two players"
+    )]
+    fn test_swiss_too_many_rounds() {
+        let _context = create_context();
+
+        let mut contract = LeagueContract::new();
+        let players = ["Alice", "Bob", "Charly", "Dave"];
+        create_swiss_league(&mut contract, "Swiss", &players, 3);
+    }
+
+    /// Three players who met everybody else cannot be paired, but only after trying every pairing of the others
+    #[test]
+    fn test_swiss_pairing_gives_up() {
+        let ranking = (0..MAX_PLAYERS).collect::<Vec<u8>>();
+        let trio = |player: u8| player >= MAX_PLAYERS - 3;
+        let next = swiss::next_round(&ranking, &[], |a, b| trio(a) != trio(b));
+        assert!(next.is_none());
+    }
+
+    #[test]
+    #[should_panic(expected = "League is not played in the swiss format")]
+    fn test_round_robin_has_no_swiss_rounds() {
+        let _context = create_context();
+
+        let mut contract = LeagueContract::new();
+        create_four_player_league(&mut contract, "League", 1, LeagueOptions::default());
+        contract.get_swiss_rounds("League".to_string());
+    }
+
+    #[test]
+    fn test_buchholz_and_sonneborn_berger() {
+        let _context = create_context();
+
+        let mut contract = LeagueContract::new();
+        for (name, tiebreakers) in [
+            ("Buchholz", vec![Tiebreaker::Buchholz]),
+            (
+                "SonnebornBerger",
+                vec![Tiebreaker::Buchholz, Tiebreaker::SonnebornBerger],
+            ),
+        ] {
+            create_four_player_league(
+                &mut contract,
+                name,
+                1,
+                LeagueOptions {
+                    tiebreakers: Some(tiebreakers),
+                    ..Default::default()
+                },
+            );
+            play_match(&mut contract, name, ("Dave", "Bob"), &[SecondPlayerWon]);
+            play_match(&mut contract, name, ("Dave", "Charly"), &[Draw]);
+            play_match(&mut contract, name, ("Alice", "Dave"), &[SecondPlayerWon]);
+            play_match(&mut contract, name, ("Bob", "Charly"), &[SecondPlayerWon]);
+            play_match(&mut contract, name, ("Alice", "Bob"), &[FirstPlayerWon]);
+            play_match(&mut contract, name, ("Alice", "Charly"), &[Draw]);
+        }
+
+        // Alice and Dave have 4 points and opponents with 12 points in total
+        assert_eq!(
+            vec!["Charly", "Alice", "Dave", "Bob"],
+            standings_order(&contract, "Buchholz")
+        );
+        // Dave beat Alice with 4 points while Alice only beat Bob with 3 points
+        assert_eq!(
+            vec!["Charly", "Dave", "Alice", "Bob"],
+            standings_order(&contract, "SonnebornBerger")
+        );
+    }
+
+    /// Creates a Bo1 double round robin of Alice, Bob and Charly
+    fn create_double_round_robin(contract: &mut LeagueContract, name: &str) {
+        contract.create_league(
+            name.to_string(),
+            vec!["Alice".to_string(), "Bob".to_string(), "Charly".to_string()],
+            Vec::new(),
+            1,
+            StandardGameType,
+            Some(LeagueOptions {
+                format: DoubleRoundRobin,
+                ..Default::default()
+            }),
+        );
+    }
+
+    #[test]
+    fn test_double_round_robin_legs() {
+        let _context = create_context();
+
+        let mut contract = LeagueContract::new();
+        create_double_round_robin(&mut contract, "League");
+
+        // The home player wins in both legs
+        play_match(&mut contract, "League", ("Alice", "Bob"), &[FirstPlayerWon]);
+        play_match(&mut contract, "League", ("Bob", "Alice"), &[FirstPlayerWon]);
+
+        let mut legs = contract
+            .get_league("League".to_string())
+            .matches
+            .into_iter()
+            .map(|m| (m.leg, m.first_player, m.second_player, m.winner))
+            .collect::<Vec<_>>();
+        legs.sort_by_key(|(leg, ..)| *leg);
+        assert_eq!(2, legs.len());
+        assert_eq!(
+            (Some(1), "Alice".to_string(), "Bob".to_string()),
+            (legs[0].0, legs[0].1.clone(), legs[0].2.clone())
+        );
+        assert!(matches!(legs[0].3, Winner::FirstPlayer));
+        assert_eq!(
+            (Some(2), "Alice".to_string(), "Bob".to_string()),
+            (legs[1].0, legs[1].1.clone(), legs[1].2.clone())
+        );
+        assert!(matches!(legs[1].3, Winner::SecondPlayer));
+
+        let standings = contract.get_standings("League".to_string());
+        assert_eq!((2, 3), (standings[0].matches_played, standings[0].points));
+        assert_eq!((2, 3), (standings[1].matches_played, standings[1].points));
+    }
+
+    #[test]
+    fn test_double_round_robin_legs_in_sequence() {
+        let _context = create_context();
+
+        let mut contract = LeagueContract::new();
+        create_double_round_robin(&mut contract, "League");
+
+        // Naming the players in the same order still plays the second leg after the first
+        play_match(&mut contract, "League", ("Alice", "Bob"), &[FirstPlayerWon]);
+        play_match(&mut contract, "League", ("Alice", "Bob"), &[FirstPlayerWon]);
+
+        let mut legs = contract
+            .get_league("League".to_string())
+            .matches
+            .into_iter()
+            .map(|m| (m.leg, m.winner))
+            .collect::<Vec<_>>();
+        legs.sort_by_key(|(leg, _)| *leg);
+        assert_eq!(2, legs.len());
+        assert_eq!(Some(1), legs[0].0);
+        assert!(matches!(legs[0].1, Winner::FirstPlayer));
+        assert_eq!(Some(2), legs[1].0);
+        assert!(matches!(legs[1].1, Winner::FirstPlayer));
+    }
+
+    #[test]
+    #[should_panic(expected = "Match is already finished")]
+    fn test_double_round_robin_leg_finished() {
+        let _context = create_context();
+
+        let mut contract = LeagueContract::new();
+        create_double_round_robin(&mut contract, "League");
+        play_match(&mut contract, "League", ("Alice", "Bob"), &[FirstPlayerWon]);
+        play_match(&mut contract, "League", ("Bob", "Alice"), &[FirstPlayerWon]);
+        play_match(&mut contract, "League", ("Alice", "Bob"), &[FirstPlayerWon]);
+    }
+
+    #[test]
+    #[should_panic(expected = "League is not finished yet")]
+    fn test_double_round_robin_first_legs_only() {
+        let _context = create_context();
+
+        let mut contract = LeagueContract::new();
+        create_double_round_robin(&mut contract, "League");
+        play_match(&mut contract, "League", ("Alice", "Bob"), &[FirstPlayerWon]);
+        play_match(
+            &mut contract,
+            "League",
+            ("Alice", "Charly"),
+            &[FirstPlayerWon],
+        );
+        play_match(
+            &mut contract,
+            "League",
+            ("Bob", "Charly"),
+            &[FirstPlayerWon],
+        );
+        contract.delete_league("League".to_string(), false);
+    }
+
+    #[test]
+    fn test_double_round_robin_finished() {
+        let _context = create_context();
+
+        let mut contract = LeagueContract::new();
+        create_double_round_robin(&mut contract, "League");
+        for (home, away) in [("Alice", "Bob"), ("Alice", "Charly"), ("Bob", "Charly")] {
+            play_match(&mut contract, "League", (home, away), &[FirstPlayerWon]);
+            play_match(&mut contract, "League", (away, home), &[Draw]);
+        }
+        assert_eq!(6, contract.get_league("League".to_string()).matches.len());
+        contract.delete_league("League".to_string(), false);
+    }
+
+    /// Creates a Bo1 league with two groups of which the best two advance
+    fn create_group_league(
+        contract: &mut LeagueContract,
+        name: &str,
+        players: &[&str],
+        groups: Option<Vec<u8>>,
+    ) {
+        contract.create_league(
+            name.to_string(),
+            players.iter().map(|p| p.to_string()).collect(),
+            Vec::new(),
+            1,
+            StandardGameType,
+            Some(LeagueOptions {
+                format: GroupsAndPlayoffs {
+                    groups: 2,
+                    advance: 2,
+                },
+                groups,
+                ..Default::default()
+            }),
+        );
+    }
+
+    /// Returns the player names of every group in the order of their standings
+    fn group_tables(contract: &LeagueContract, name: &str) -> Vec<Vec<String>> {
+        contract
+            .get_group_standings(name.to_string())
+            .into_iter()
+            .map(|group| group.into_iter().map(|e| e.player).collect())
+            .collect()
+    }
+
+    #[test]
+    fn test_groups_and_playoffs() {
+        let _context = create_context();
+
+        let mut contract = LeagueContract::new();
+        let players = ["Alice", "Bob", "Charly", "Dave", "Eve", "Frank"];
+        create_group_league(&mut contract, "Cup", &players, None);
+
+        // The players are distributed in snake order
+        assert_eq!(
+            vec![vec!["Alice", "Dave", "Eve"], vec!["Bob", "Charly", "Frank"]],
+            group_tables(&contract, "Cup")
+        );
+
+        play_match(&mut contract, "Cup", ("Alice", "Dave"), &[FirstPlayerWon]);
+        play_match(&mut contract, "Cup", ("Alice", "Eve"), &[FirstPlayerWon]);
+        play_match(&mut contract, "Cup", ("Eve", "Dave"), &[FirstPlayerWon]);
+        play_match(&mut contract, "Cup", ("Frank", "Bob"), &[FirstPlayerWon]);
+        play_match(&mut contract, "Cup", ("Frank", "Charly"), &[FirstPlayerWon]);
+        assert!(contract.get_bracket("Cup".to_string()).is_empty());
+        assert!(matches!(
+            contract.get_league("Cup".to_string()).phase,
+            Phase::Regular
+        ));
+
+        play_match(&mut contract, "Cup", ("Bob", "Charly"), &[FirstPlayerWon]);
+        assert_eq!(
+            vec![vec!["Alice", "Eve", "Dave"], vec!["Frank", "Bob", "Charly"]],
+            group_tables(&contract, "Cup")
+        );
+        assert!(matches!(
+            contract.get_league("Cup".to_string()).phase,
+            Phase::Playoffs
+        ));
+
+        // The group winners face the runners-up of the other group
+        assert_eq!(
+            vec![
+                (Upper, 1, player("Alice"), player("Bob"), Pending),
+                (Upper, 1, player("Frank"), player("Eve"), Pending),
+                (Upper, 2, Pending, Pending, Pending),
+            ],
+            bracket_of(&contract, "Cup")
+        );
+        play_match(&mut contract, "Cup", ("Alice", "Bob"), &[FirstPlayerWon]);
+        play_match(&mut contract, "Cup", ("Frank", "Eve"), &[FirstPlayerWon]);
+        play_match(&mut contract, "Cup", ("Alice", "Frank"), &[SecondPlayerWon]);
+        contract.delete_league("Cup".to_string(), false);
+    }
+
+    #[test]
+    fn test_playoff_rematch_of_group() {
+        let _context = create_context();
+
+        let mut contract = LeagueContract::new();
+        let players = ["Alice", "Bob", "Charly", "Dave"];
+        create_group_league(&mut contract, "Cup", &players, Some(vec![0, 0, 1, 1]));
+
+        play_match(&mut contract, "Cup", ("Alice", "Bob"), &[FirstPlayerWon]);
+        play_match(&mut contract, "Cup", ("Charly", "Dave"), &[FirstPlayerWon]);
+        play_match(&mut contract, "Cup", ("Alice", "Dave"), &[FirstPlayerWon]);
+        play_match(&mut contract, "Cup", ("Charly", "Bob"), &[SecondPlayerWon]);
+
+        // Alice and Bob meet again in the final, which is a match of its own
+        play_match(&mut contract, "Cup", ("Alice", "Bob"), &[SecondPlayerWon]);
+        let mut meetings = contract
+            .get_league("Cup".to_string())
+            .matches
+            .into_iter()
+            .filter(|m| m.first_player == "Alice" && m.second_player == "Bob")
+            .map(|m| (m.series, m.winner))
+            .collect::<Vec<_>>();
+        meetings.sort_by_key(|(series, _)| *series);
+        assert_eq!(2, meetings.len());
+        assert!(matches!(meetings[0], (0, Winner::FirstPlayer)));
+        assert!(matches!(meetings[1], (1, Winner::SecondPlayer)));
+
+        // The standings of the league count the playoffs, the group standings do not
+        let standings = contract.get_standings("Cup".to_string());
+        let alice = standings.iter().find(|e| e.player == "Alice").unwrap();
+        assert_eq!(3, alice.matches_played);
+        let groups = contract.get_group_standings("Cup".to_string());
+        let alice = groups[0].iter().find(|e| e.player == "Alice").unwrap();
+        assert_eq!(1, alice.matches_played);
+        contract.delete_league("Cup".to_string(), false);
+    }
+
+    #[test]
+    #[should_panic(expected = "Players are not in the same group")]
+    fn test_groups_cross_group_match() {
+        let _context = create_context();
+
+        let mut contract = LeagueContract::new();
+        let players = ["Alice", "Bob", "Charly", "Dave"];
+        create_group_league(&mut contract, "Cup", &players, None);
+        play_match(&mut contract, "Cup", ("Alice", "Bob"), &[FirstPlayerWon]);
+    }
+
+    #[test]
+    #[should_panic(expected = "Players do not face each other in the current bracket")]
+    fn test_playoffs_only_bracket_matches() {
+        let _context = create_context();
+
+        let mut contract = LeagueContract::new();
+        let players = ["Alice", "Bob", "Charly", "Dave"];
+        create_group_league(&mut contract, "Cup", &players, Some(vec![0, 0, 1, 1]));
+        play_match(&mut contract, "Cup", ("Alice", "Bob"), &[FirstPlayerWon]);
+        play_match(&mut contract, "Cup", ("Charly", "Dave"), &[FirstPlayerWon]);
+        play_match(&mut contract, "Cup", ("Alice", "Charly"), &[FirstPlayerWon]);
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "Every group needs at least two players and at least as many as advance"
+    )]
+    fn test_groups_too_small() {
+        let _context = create_context();
+
+        let mut contract = LeagueContract::new();
+        let players = ["Alice", "Bob", "Charly", "Dave"];
+        create_group_league(&mut contract, "Cup", &players, Some(vec![0, 0, 0, 1]));
+    }
+
+    #[test]
+    #[should_panic(expected = "Only a league with groups can assign groups")]
+    fn test_groups_without_group_format() {
+        let _context = create_context();
+
+        let mut contract = LeagueContract::new();
+        create_four_player_league(
+            &mut contract,
+            "League",
+            1,
+            LeagueOptions {
+                groups: Some(vec![0, 0, 1, 1]),
+                ..Default::default()
+            },
+        );
+    }
+
+    /// Returns the best_of and the winner of the matches between both players in any order
+    fn match_of(contract: &LeagueContract, name: &str, players: (&str, &str)) -> (u8, Winner) {
+        let found = contract
+            .get_league(name.to_string())
+            .matches
+            .into_iter()
+            .find(|m| {
+                (m.first_player == players.0 && m.second_player == players.1)
+                    || (m.first_player == players.1 && m.second_player == players.0)
+            })
+            .unwrap();
+        (found.best_of, found.winner)
+    }
+
+    #[test]
+    fn test_best_of_per_stage_and_round() {
+        let _context = create_context();
+
+        let mut contract = LeagueContract::new();
+        let players = ["Alice", "Bob", "Charly", "Dave"];
+        contract.create_league(
+            "Cup".to_string(),
+            players.iter().map(|p| p.to_string()).collect(),
+            Vec::new(),
+            1,
+            StandardGameType,
+            Some(LeagueOptions {
+                format: GroupsAndPlayoffs {
+                    groups: 2,
+                    advance: 2,
+                },
+                groups: Some(vec![0, 0, 1, 1]),
+                best_of_overrides: vec![
+                    BestOfOverride::Stage {
+                        stage: Stage::Regular,
+                        best_of: 3,
+                    },
+                    BestOfOverride::Stage {
+                        stage: Stage::Upper,
+                        best_of: 5,
+                    },
+                    BestOfOverride::Round {
+                        stage: Stage::Upper,
+                        round: 2,
+                        best_of: 7,
+                    },
+                ],
+                ..Default::default()
+            }),
+        );
+
+        // The groups are played as Bo3
+        play_match(&mut contract, "Cup", ("Alice", "Bob"), &[FirstPlayerWon]);
+        assert!(matches!(
+            match_of(&contract, "Cup", ("Alice", "Bob")),
+            (3, Winner::Undecided)
+        ));
+        play_match(&mut contract, "Cup", ("Alice", "Bob"), &[FirstPlayerWon]);
+        play_match(
+            &mut contract,
+            "Cup",
+            ("Charly", "Dave"),
+            &[FirstPlayerWon; 2],
+        );
+        assert!(matches!(
+            match_of(&contract, "Cup", ("Alice", "Bob")),
+            (3, Winner::FirstPlayer)
+        ));
+
+        // The semi finals are played as Bo5
+        play_match(
+            &mut contract,
+            "Cup",
+            ("Alice", "Dave"),
+            &[FirstPlayerWon; 2],
+        );
+        assert!(matches!(
+            match_of(&contract, "Cup", ("Alice", "Dave")),
+            (5, Winner::Undecided)
+        ));
+        play_match(&mut contract, "Cup", ("Alice", "Dave"), &[FirstPlayerWon]);
+        play_match(
+            &mut contract,
+            "Cup",
+            ("Charly", "Bob"),
+            &[FirstPlayerWon; 3],
+        );
+
+        // And the final as Bo7
+        play_match(
+            &mut contract,
+            "Cup",
+            ("Alice", "Charly"),
+            &[FirstPlayerWon; 3],
+        );
+        assert!(matches!(
+            match_of(&contract, "Cup", ("Alice", "Charly")),
+            (7, Winner::Undecided)
+        ));
+        play_match(&mut contract, "Cup", ("Alice", "Charly"), &[FirstPlayerWon]);
+        assert!(matches!(
+            match_of(&contract, "Cup", ("Alice", "Charly")),
+            (7, Winner::FirstPlayer)
+        ));
+        contract.delete_league("Cup".to_string(), false);
+    }
+
+    #[test]
+    fn test_best_of_match_override() {
+        let _context = create_context();
+
+        let mut contract = LeagueContract::new();
+        create_four_player_league(
+            &mut contract,
+            "League",
+            1,
+            LeagueOptions {
+                best_of_overrides: vec![
+                    BestOfOverride::Match {
+                        players: ("Bob".to_string(), "Alice".to_string()),
+                        series: 0,
+                        best_of: 5,
+                    },
+                    BestOfOverride::Stage {
+                        stage: Stage::Regular,
+                        best_of: 3,
+                    },
+                ],
+                ..Default::default()
+            },
+        );
+
+        // The rule for the match wins over the rule for the stage
+        play_match(
+            &mut contract,
+            "League",
+            ("Alice", "Bob"),
+            &[FirstPlayerWon; 2],
+        );
+        assert!(matches!(
+            match_of(&contract, "League", ("Alice", "Bob")),
+            (5, Winner::Undecided)
+        ));
+        play_match(
+            &mut contract,
+            "League",
+            ("Charly", "Dave"),
+            &[FirstPlayerWon; 2],
+        );
+        assert!(matches!(
+            match_of(&contract, "League", ("Charly", "Dave")),
+            (3, Winner::FirstPlayer)
+        ));
+        assert_eq!(1, contract.get_league("League".to_string()).best_of);
+    }
+
+    #[test]
+    #[should_panic(expected = "best_of number should be odd")]
+    fn test_best_of_override_even() {
+        let _context = create_context();
+
+        let mut contract = LeagueContract::new();
+        create_four_player_league(
+            &mut contract,
+            "League",
+            1,
+            LeagueOptions {
+                best_of_overrides: vec![BestOfOverride::Stage {
+                    stage: Stage::Regular,
+                    best_of: 2,
+                }],
+                ..Default::default()
+            },
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "A best_of override needs two different players of the league")]
+    fn test_best_of_override_unknown_player() {
+        let _context = create_context();
+
+        let mut contract = LeagueContract::new();
+        create_four_player_league(
+            &mut contract,
+            "League",
+            1,
+            LeagueOptions {
+                best_of_overrides: vec![BestOfOverride::Match {
+                    players: ("Alice".to_string(), "Eve".to_string()),
+                    series: 0,
+                    best_of: 3,
+                }],
+                ..Default::default()
+            },
+        );
+    }
+
+    /// Returns the pairings of every matchday and the bye
+    fn schedule_of(contract: &LeagueContract, name: &str) -> Vec<Round> {
+        contract
+            .get_schedule(name.to_string())
+            .into_iter()
+            .map(|m| (m.pairings, m.bye))
+            .collect()
+    }
+
+    #[test]
+    fn test_schedule_even() {
+        let _context = create_context();
+
+        let mut contract = LeagueContract::new();
+        create_four_player_league(&mut contract, "League", 1, LeagueOptions::default());
+
+        let schedule = schedule_of(&contract, "League");
+        assert_eq!(3, schedule.len());
+        let mut all = Vec::new();
+        for (pairings, bye) in schedule {
+            assert_eq!(None, bye);
+            // Everybody plays exactly once per matchday
+            let mut players = pairings
+                .iter()
+                .flat_map(|(a, b)| [a.clone(), b.clone()])
+                .collect::<Vec<String>>();
+            players.sort();
+            assert_eq!(vec!["Alice", "Bob", "Charly", "Dave"], players);
+            all.extend(pairings);
+        }
+        all.sort();
+        all.dedup();
+        assert_eq!(6, all.len());
+    }
+
+    #[test]
+    fn test_schedule_odd_byes() {
+        let _context = create_context();
+
+        let mut contract = LeagueContract::new();
+        let players = ["Alice", "Bob", "Charly", "Dave", "Eve"];
+        contract.create_league(
+            "League".to_string(),
+            players.iter().map(|p| p.to_string()).collect(),
+            Vec::new(),
+            1,
+            StandardGameType,
+            None,
+        );
+
+        let schedule = schedule_of(&contract, "League");
+        assert_eq!(5, schedule.len());
+        assert!(schedule.iter().all(|(pairings, _)| pairings.len() == 2));
+        let mut byes = schedule
+            .into_iter()
+            .map(|(_, bye)| bye.unwrap())
+            .collect::<Vec<String>>();
+        byes.sort();
+        assert_eq!(players.to_vec(), byes);
+    }
+
+    #[test]
+    fn test_schedule_double_round_robin() {
+        let _context = create_context();
+
+        let mut contract = LeagueContract::new();
+        create_double_round_robin(&mut contract, "League");
+
+        // The second half repeats the first one with swapped home and away
+        let schedule = schedule_of(&contract, "League");
+        assert_eq!(6, schedule.len());
+        for (first_leg, second_leg) in schedule[..3].iter().zip(schedule[3..].iter()) {
+            let swapped = first_leg
+                .0
+                .iter()
+                .map(|(home, away)| (away.clone(), home.clone()))
+                .collect::<Vec<(String, String)>>();
+            assert_eq!(swapped, second_leg.0);
+        }
+        assert_eq!(("Alice".to_string(), "Bob".to_string()), schedule[0].0[0]);
+        assert_eq!(Some("Charly".to_string()), schedule[0].1);
+    }
+
+    #[test]
+    fn test_schedule_home_and_away() {
+        let _context = create_context();
+
+        let mut contract = LeagueContract::new();
+        let players = ["Alice", "Bob", "Charly", "Dave", "Eve", "Frank"];
+        contract.create_league(
+            "League".to_string(),
+            players.iter().map(|p| p.to_string()).collect(),
+            Vec::new(),
+            1,
+            StandardGameType,
+            None,
+        );
+
+        // Everybody alternates at least every other matchday and is as often at home as away
+        let schedule = schedule_of(&contract, "League");
+        for player in players {
+            let at_home = schedule
+                .iter()
+                .map(|(pairings, _)| pairings.iter().any(|(home, _)| home == player))
+                .collect::<Vec<bool>>();
+            assert!(at_home
+                .windows(3)
+                .all(|days| days.iter().any(|home| *home != days[0])));
+            let home_matches = at_home.iter().filter(|home| **home).count();
+            assert!((2..=3).contains(&home_matches));
+        }
+    }
+
+    #[test]
+    fn test_strict_schedule() {
+        let _context = create_context();
+
+        let mut contract = LeagueContract::new();
+        create_four_player_league(
+            &mut contract,
+            "League",
+            1,
+            LeagueOptions {
+                strict_schedule: true,
+                ..Default::default()
+            },
+        );
+
+        // The second matchday is open as soon as the first one is finished
+        let schedule = schedule_of(&contract, "League");
+        for (pairings, _) in schedule {
+            for (home, away) in pairings {
+                contract.add_game(
+                    "League".to_string(),
+                    (home, away),
+                    FirstPlayerWon,
+                    "{}".to_string(),
+                );
+            }
+        }
+        assert_eq!(6, contract.get_league("League".to_string()).matches.len());
+    }
+
+    #[test]
+    #[should_panic(expected = "Match is on a future matchday")]
+    fn test_strict_schedule_future_match() {
+        let _context = create_context();
+
+        let mut contract = LeagueContract::new();
+        create_four_player_league(
+            &mut contract,
+            "League",
+            1,
+            LeagueOptions {
+                strict_schedule: true,
+                ..Default::default()
+            },
+        );
+
+        let (pairings, _) = schedule_of(&contract, "League").remove(1);
+        let (home, away) = pairings[0].clone();
+        contract.add_game(
+            "League".to_string(),
+            (home, away),
+            FirstPlayerWon,
+            "{}".to_string(),
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "Only a round robin league can have a strict schedule")]
+    fn test_strict_schedule_knockout() {
+        let _context = create_context();
+
+        let mut contract = LeagueContract::new();
+        create_four_player_league(
+            &mut contract,
+            "League",
+            1,
+            LeagueOptions {
+                format: SingleElimination,
+                strict_schedule: true,
+                ..Default::default()
+            },
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "League is not played as a round robin")]
+    fn test_schedule_swiss() {
+        let _context = create_context();
+
+        let mut contract = LeagueContract::new();
+        create_swiss_league(
+            &mut contract,
+            "League",
+            &["Alice", "Bob", "Charly", "Dave"],
+            1,
+        );
+        contract.get_schedule("League".to_string());
+    }
+
+    #[test]
+    fn test_best_of_per_matchday() {
+        let _context = create_context();
+
+        let mut contract = LeagueContract::new();
+        create_four_player_league(
+            &mut contract,
+            "League",
+            1,
+            LeagueOptions {
+                best_of_overrides: vec![BestOfOverride::Round {
+                    stage: Stage::Regular,
+                    round: 3,
+                    best_of: 3,
+                }],
+                ..Default::default()
+            },
+        );
+
+        let schedule = schedule_of(&contract, "League");
+        let (home, away) = schedule[2].0[0].clone();
+        play_match(&mut contract, "League", (&home, &away), &[FirstPlayerWon]);
+        assert!(matches!(
+            match_of(&contract, "League", (&home, &away)),
+            (3, Winner::Undecided)
+        ));
+        let (home, away) = schedule[0].0[0].clone();
+        play_match(&mut contract, "League", (&home, &away), &[FirstPlayerWon]);
+        assert!(matches!(
+            match_of(&contract, "League", (&home, &away)),
+            (1, Winner::FirstPlayer | Winner::SecondPlayer)
+        ));
+    }
+
+    /// Returns the winner and the forfeit reason of the match between both players in any order
+    fn forfeit_of(
+        contract: &LeagueContract,
+        name: &str,
+        players: (&str, &str),
+    ) -> (Winner, Option<ForfeitReason>) {
+        let found = contract
+            .get_league(name.to_string())
+            .matches
+            .into_iter()
+            .find(|m| {
+                (m.first_player == players.0 && m.second_player == players.1)
+                    || (m.first_player == players.1 && m.second_player == players.0)
+            })
+            .unwrap();
+        (found.winner, found.forfeit)
+    }
+
+    #[test]
+    fn test_deadline_double_forfeit() {
+        let mut context = create_context();
+
+        let mut contract = LeagueContract::new();
+        create_four_player_league(
+            &mut contract,
+            "League",
+            1,
+            LeagueOptions {
+                deadline: Some(U64(1000)),
+                ..Default::default()
+            },
+        );
+        play_match(&mut contract, "League", ("Alice", "Bob"), &[FirstPlayerWon]);
+
+        // Nothing is overdue before the deadline
+        assert_eq!(0, contract.forfeit_overdue_matches("League".to_string()));
+
+        testing_env!(context.block_timestamp(2000).build());
+        assert_eq!(5, contract.forfeit_overdue_matches("League".to_string()));
+        assert!(matches!(
+            forfeit_of(&contract, "League", ("Alice", "Bob")),
+            (Winner::FirstPlayer, None)
+        ));
+        assert!(matches!(
+            forfeit_of(&contract, "League", ("Charly", "Dave")),
+            (Winner::NoWinner, Some(ForfeitReason::Deadline))
+        ));
+
+        let standings = contract.get_standings("League".to_string());
+        assert_eq!("Alice", standings[0].player);
+        let alice = &standings[0];
+        assert_eq!(
+            (3, 1, 0, 2),
+            (
+                alice.points,
+                alice.matches_won,
+                alice.walkovers,
+                alice.forfeits
+            )
+        );
+        let charly = standings.iter().find(|e| e.player == "Charly").unwrap();
+        assert_eq!(
+            (0, 3, 3),
+            (charly.points, charly.matches_lost, charly.forfeits)
+        );
+
+        // Every match is finished now
+        contract.delete_league("League".to_string(), false);
+    }
+
+    #[test]
+    fn test_deadline_walkover() {
+        let mut context = create_context();
+
+        let mut contract = LeagueContract::new();
+        create_four_player_league(
+            &mut contract,
+            "League",
+            3,
+            LeagueOptions {
+                deadline: Some(U64(1000)),
+                forfeit_policy: ForfeitPolicy::Walkover,
+                ..Default::default()
+            },
+        );
+        play_match(&mut contract, "League", ("Bob", "Alice"), &[FirstPlayerWon]);
+
+        testing_env!(context.block_timestamp(2000).build());
+        contract.forfeit_overdue_matches("League".to_string());
+
+        // Bob was ahead, the matches without a game are lost by both
+        assert!(matches!(
+            forfeit_of(&contract, "League", ("Alice", "Bob")),
+            (Winner::SecondPlayer, Some(ForfeitReason::Deadline))
+        ));
+        assert!(matches!(
+            forfeit_of(&contract, "League", ("Alice", "Charly")),
+            (Winner::NoWinner, Some(ForfeitReason::Deadline))
+        ));
+        let standings = contract.get_standings("League".to_string());
+        let bob = standings.iter().find(|e| e.player == "Bob").unwrap();
+        assert_eq!((1, 1, 2), (bob.walkovers, bob.games_won, bob.forfeits));
+        let alice = standings.iter().find(|e| e.player == "Alice").unwrap();
+        assert_eq!(3, alice.forfeits);
+    }
+
+    #[test]
+    fn test_deadline_walkover_unplayed() {
+        let mut context = create_context();
+
+        let mut contract = LeagueContract::new();
+        create_four_player_league(
+            &mut contract,
+            "League",
+            3,
+            LeagueOptions {
+                deadline: Some(U64(1000)),
+                forfeit_policy: ForfeitPolicy::Walkover,
+                ..Default::default()
+            },
+        );
+        play_match(
+            &mut contract,
+            "League",
+            ("Alice", "Bob"),
+            &[FirstPlayerWon, SecondPlayerWon],
+        );
+
+        // Neither the even nor the unplayed match has a player ahead
+        testing_env!(context.block_timestamp(2000).build());
+        assert_eq!(6, contract.forfeit_overdue_matches("League".to_string()));
+        assert!(matches!(
+            forfeit_of(&contract, "League", ("Alice", "Bob")),
+            (Winner::NoWinner, Some(ForfeitReason::Deadline))
+        ));
+        assert!(matches!(
+            forfeit_of(&contract, "League", ("Charly", "Dave")),
+            (Winner::NoWinner, Some(ForfeitReason::Deadline))
+        ));
+        let standings = contract.get_standings("League".to_string());
+        assert!(standings
+            .iter()
+            .all(|e| e.walkovers == 0 && e.forfeits == 3));
+    }
+
+    #[test]
+    fn test_matchday_deadlines() {
+        let mut context = create_context();
+
+        let mut contract = LeagueContract::new();
+        create_four_player_league(
+            &mut contract,
+            "League",
+            1,
+            LeagueOptions {
+                matchday_deadlines: vec![U64(1000), U64(3000)],
+                ..Default::default()
+            },
+        );
+        let deadlines = contract
+            .get_schedule("League".to_string())
+            .into_iter()
+            .map(|m| m.deadline.map(|d| d.0))
+            .collect::<Vec<Option<u64>>>();
+        assert_eq!(vec![Some(1000), Some(3000), None], deadlines);
+
+        // Only the first matchday is overdue
+        testing_env!(context.block_timestamp(2000).build());
+        assert_eq!(2, contract.forfeit_overdue_matches("League".to_string()));
+        let (pairings, _) = schedule_of(&contract, "League").remove(0);
+        for (home, away) in pairings {
+            assert!(matches!(
+                forfeit_of(&contract, "League", (&home, &away)),
+                (Winner::NoWinner, Some(ForfeitReason::Deadline))
+            ));
+        }
+        assert_eq!(2, contract.get_league("League".to_string()).matches.len());
+    }
+
+    #[test]
+    fn test_deadline_bracket() {
+        let mut context = create_context();
+
+        let mut contract = LeagueContract::new();
+        create_four_player_league(
+            &mut contract,
+            "Cup",
+            1,
+            LeagueOptions {
+                format: SingleElimination,
+                deadline: Some(U64(1000)),
+                forfeit_policy: ForfeitPolicy::Walkover,
+                ..Default::default()
+            },
+        );
+        play_match(&mut contract, "Cup", ("Alice", "Dave"), &[FirstPlayerWon]);
+
+        // Bob and Charly are both out, so Alice advances without a final
+        testing_env!(context.block_timestamp(2000).build());
+        assert_eq!(1, contract.forfeit_overdue_matches("Cup".to_string()));
+        assert_eq!(
+            vec![
+                (Upper, 1, player("Alice"), player("Dave"), player("Alice")),
+                (Upper, 1, player("Bob"), player("Charly"), Bye),
+                (Upper, 2, player("Alice"), Bye, player("Alice")),
+            ],
+            bracket_of(&contract, "Cup")
+        );
+        contract.delete_league("Cup".to_string(), false);
+    }
+
+    #[test]
+    #[should_panic(expected = "You may not forfeit matches of the league")]
+    fn test_forfeit_overdue_untrusted() {
+        let mut context = create_context();
+
+        let mut contract = LeagueContract::new();
+        create_four_player_league(&mut contract, "League", 1, LeagueOptions::default());
+
+        testing_env!(context.predecessor_account_id(accounts(2)).build());
+        contract.forfeit_overdue_matches("League".to_string());
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "Matchday deadlines need a round robin with at least as many matchdays"
+    )]
+    fn test_matchday_deadlines_swiss() {
+        let _context = create_context();
+
+        let mut contract = LeagueContract::new();
+        create_four_player_league(
+            &mut contract,
+            "League",
+            1,
+            LeagueOptions {
+                format: Swiss { rounds: 2 },
+                matchday_deadlines: vec![U64(1000)],
+                ..Default::default()
+            },
+        );
+    }
+
+    #[test]
+    fn test_record_forfeit() {
+        let _context = create_context();
+
+        let mut contract = LeagueContract::new();
+        create_four_player_league(&mut contract, "League", 3, LeagueOptions::default());
+        contract.record_forfeit(
+            "League".to_string(),
+            ("Bob".to_string(), "Alice".to_string()),
+            Some("Alice".to_string()),
+            "Bob did not show up".to_string(),
+        );
+
+        let summary = contract.get_league("League".to_string());
+        assert_eq!(1, summary.matches.len());
+        assert!(summary.matches[0].games.is_empty());
+        assert!(matches!(summary.matches[0].winner, Winner::FirstPlayer));
+        assert_eq!(
+            Some(ForfeitReason::Recorded("Bob did not show up".to_string())),
+            summary.matches[0].forfeit
+        );
+
+        let standings = contract.get_standings("League".to_string());
+        assert_eq!("Alice", standings[0].player);
+        let alice = &standings[0];
+        assert_eq!((3, 1, 0), (alice.points, alice.walkovers, alice.games_won));
+        let bob = standings.iter().find(|e| e.player == "Bob").unwrap();
+        assert_eq!((1, 1), (bob.matches_lost, bob.forfeits));
+    }
+
+    #[test]
+    fn test_record_double_forfeit_finishes_league() {
+        let _context = create_context();
+
+        let mut contract = LeagueContract::new();
+        create_four_player_league(&mut contract, "League", 1, LeagueOptions::default());
+        play_match(&mut contract, "League", ("Alice", "Bob"), &[FirstPlayerWon]);
+        play_match(
+            &mut contract,
+            "League",
+            ("Alice", "Charly"),
+            &[FirstPlayerWon],
+        );
+        play_match(
+            &mut contract,
+            "League",
+            ("Alice", "Dave"),
+            &[FirstPlayerWon],
+        );
+        play_match(
+            &mut contract,
+            "League",
+            ("Bob", "Charly"),
+            &[FirstPlayerWon],
+        );
+        play_match(&mut contract, "League", ("Bob", "Dave"), &[FirstPlayerWon]);
+        contract.record_forfeit(
+            "League".to_string(),
+            ("Charly".to_string(), "Dave".to_string()),
+            None,
+            "Both did not show up".to_string(),
+        );
+        assert!(matches!(
+            forfeit_of(&contract, "League", ("Charly", "Dave")),
+            (Winner::NoWinner, Some(ForfeitReason::Recorded(_)))
+        ));
+        contract.delete_league("League".to_string(), false);
+    }
+
+    #[test]
+    fn test_record_forfeit_in_bracket() {
+        let _context = create_context();
+
+        let mut contract = LeagueContract::new();
+        create_cup(&mut contract, "Cup", &["Alice", "Bob", "Charly", "Dave"]);
+        contract.record_forfeit(
+            "Cup".to_string(),
+            ("Alice".to_string(), "Dave".to_string()),
+            Some("Dave".to_string()),
+            "Alice did not show up".to_string(),
+        );
+        play_match(&mut contract, "Cup", ("Bob", "Charly"), &[SecondPlayerWon]);
+        play_match(&mut contract, "Cup", ("Dave", "Charly"), &[FirstPlayerWon]);
+        assert_eq!(
+            vec![
+                (Upper, 1, player("Alice"), player("Dave"), player("Dave")),
+                (Upper, 1, player("Bob"), player("Charly"), player("Charly")),
+                (Upper, 2, player("Dave"), player("Charly"), player("Dave")),
+            ],
+            bracket_of(&contract, "Cup")
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "Match is already finished")]
+    fn test_add_game_after_forfeit() {
+        let _context = create_context();
+
+        let mut contract = LeagueContract::new();
+        create_four_player_league(&mut contract, "League", 3, LeagueOptions::default());
+        contract.record_forfeit(
+            "League".to_string(),
+            ("Alice".to_string(), "Bob".to_string()),
+            Some("Bob".to_string()),
+            "No show".to_string(),
+        );
+        play_match(&mut contract, "League", ("Alice", "Bob"), &[FirstPlayerWon]);
+    }
+
+    #[test]
+    #[should_panic(expected = "Only a match without games can be forfeited")]
+    fn test_record_forfeit_after_game() {
+        let _context = create_context();
+
+        let mut contract = LeagueContract::new();
+        create_four_player_league(&mut contract, "League", 3, LeagueOptions::default());
+        play_match(&mut contract, "League", ("Alice", "Bob"), &[FirstPlayerWon]);
+        contract.record_forfeit(
+            "League".to_string(),
+            ("Alice".to_string(), "Bob".to_string()),
+            Some("Bob".to_string()),
+            "Alice left".to_string(),
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "The winner has to be one of the players")]
+    fn test_record_forfeit_other_winner() {
+        let _context = create_context();
+
+        let mut contract = LeagueContract::new();
+        create_four_player_league(&mut contract, "League", 1, LeagueOptions::default());
+        contract.record_forfeit(
+            "League".to_string(),
+            ("Alice".to_string(), "Bob".to_string()),
+            Some("Charly".to_string()),
+            "No show".to_string(),
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "You may not record forfeits in the league")]
+    fn test_record_forfeit_untrusted() {
+        let mut context = create_context();
+
+        let mut contract = LeagueContract::new();
+        create_four_player_league(&mut contract, "League", 1, LeagueOptions::default());
+
+        testing_env!(context.predecessor_account_id(accounts(2)).build());
+        contract.record_forfeit(
+            "League".to_string(),
+            ("Alice".to_string(), "Bob".to_string()),
+            None,
+            "No show".to_string(),
+        );
+    }
+
+    #[test]
+    fn test_withdraw_player_forfeits_remaining() {
+        let _context = create_context();
+
+        let mut contract = LeagueContract::new();
+        create_four_player_league(&mut contract, "League", 1, LeagueOptions::default());
+        play_match(
+            &mut contract,
+            "League",
+            ("Alice", "Dave"),
+            &[SecondPlayerWon],
+        );
+        contract.withdraw_player("League".to_string(), "Dave".to_string());
+
+        // The played match still counts, the remaining ones are won by the opponents
+        assert!(matches!(
+            forfeit_of(&contract, "League", ("Alice", "Dave")),
+            (Winner::SecondPlayer, None)
+        ));
+        assert!(matches!(
+            forfeit_of(&contract, "League", ("Bob", "Dave")),
+            (Winner::FirstPlayer, Some(ForfeitReason::Withdrawal))
+        ));
+        let standings = contract.get_standings("League".to_string());
+        let dave = standings.iter().find(|e| e.player == "Dave").unwrap();
+        assert_eq!(
+            (true, 3, 1, 2),
+            (dave.withdrawn, dave.points, dave.matches_won, dave.forfeits)
+        );
+        assert_eq!(
+            vec!["Dave".to_string()],
+            contract.get_league("League".to_string()).withdrawn
+        );
+
+        play_match(&mut contract, "League", ("Alice", "Bob"), &[FirstPlayerWon]);
+        play_match(
+            &mut contract,
+            "League",
+            ("Alice", "Charly"),
+            &[FirstPlayerWon],
+        );
+        play_match(
+            &mut contract,
+            "League",
+            ("Bob", "Charly"),
+            &[FirstPlayerWon],
+        );
+        contract.delete_league("League".to_string(), false);
+    }
+
+    #[test]
+    fn test_withdraw_player_annuls_played() {
+        let _context = create_context();
+
+        let mut contract = LeagueContract::new();
+        create_four_player_league(
+            &mut contract,
+            "League",
+            1,
+            LeagueOptions {
+                withdrawal_policy: WithdrawalPolicy {
+                    annul_played: true,
+                    drop_remaining: true,
+                },
+                ..Default::default()
+            },
+        );
+        play_match(
+            &mut contract,
+            "League",
+            ("Alice", "Dave"),
+            &[FirstPlayerWon],
+        );
+        contract.withdraw_player("League".to_string(), "Dave".to_string());
+
+        let summary = contract.get_league("League".to_string());
+        assert_eq!(1, summary.matches.len());
+        assert!(summary.matches[0].annulled);
+        let standings = contract.get_standings("League".to_string());
+        let alice = standings.iter().find(|e| e.player == "Alice").unwrap();
+        assert_eq!((0, 0), (alice.matches_played, alice.points));
+
+        // The league does not wait for the dropped matches
+        play_match(&mut contract, "League", ("Alice", "Bob"), &[FirstPlayerWon]);
+        play_match(
+            &mut contract,
+            "League",
+            ("Alice", "Charly"),
+            &[FirstPlayerWon],
+        );
+        play_match(
+            &mut contract,
+            "League",
+            ("Bob", "Charly"),
+            &[FirstPlayerWon],
+        );
+        contract.delete_league("League".to_string(), false);
+    }
+
+    #[test]
+    fn test_withdraw_player_from_bracket() {
+        let _context = create_context();
+
+        let mut contract = LeagueContract::new();
+        create_cup(&mut contract, "Cup", &["Alice", "Bob", "Charly", "Dave"]);
+        play_match(&mut contract, "Cup", ("Bob", "Charly"), &[FirstPlayerWon]);
+        contract.withdraw_player("Cup".to_string(), "Alice".to_string());
+
+        // Dave advances by walkover and Bob wins the final by walkover as well
+        contract.withdraw_player("Cup".to_string(), "Dave".to_string());
+        assert_eq!(
+            vec![
+                (Upper, 1, player("Alice"), player("Dave"), player("Dave")),
+                (Upper, 1, player("Bob"), player("Charly"), player("Bob")),
+                (Upper, 2, player("Dave"), player("Bob"), player("Bob")),
+            ],
+            bracket_of(&contract, "Cup")
+        );
+        contract.delete_league("Cup".to_string(), false);
+    }
+
+    #[test]
+    fn test_withdrawn_player_not_seeded() {
+        let _context = create_context();
+
+        let mut contract = LeagueContract::new();
+        let players = ["Alice", "Bob", "Charly", "Dave", "Eve", "Frank"];
+        create_group_league(&mut contract, "Cup", &players, None);
+        play_match(&mut contract, "Cup", ("Alice", "Dave"), &[FirstPlayerWon]);
+        play_match(&mut contract, "Cup", ("Alice", "Eve"), &[FirstPlayerWon]);
+        play_match(&mut contract, "Cup", ("Eve", "Dave"), &[FirstPlayerWon]);
+        play_match(&mut contract, "Cup", ("Frank", "Bob"), &[FirstPlayerWon]);
+        play_match(&mut contract, "Cup", ("Frank", "Charly"), &[FirstPlayerWon]);
+        contract.withdraw_player("Cup".to_string(), "Eve".to_string());
+        play_match(&mut contract, "Cup", ("Bob", "Charly"), &[FirstPlayerWon]);
+
+        // Eve stays second of her group, but Dave advances in her place
+        assert_eq!(
+            vec![vec!["Alice", "Eve", "Dave"], vec!["Frank", "Bob", "Charly"]],
+            group_tables(&contract, "Cup")
+        );
+        assert_eq!(
+            vec![
+                (Upper, 1, player("Alice"), player("Bob"), Pending),
+                (Upper, 1, player("Frank"), player("Dave"), Pending),
+                (Upper, 2, Pending, Pending, Pending),
+            ],
+            bracket_of(&contract, "Cup")
+        );
+    }
+
+    #[test]
+    fn test_swiss_ends_early_without_pairing() {
+        let _context = create_context();
+
+        let mut contract = LeagueContract::new();
+        let players = ["Alice", "Bob", "Charly", "Dave"];
+        contract.create_league(
+            "Swiss".to_string(),
+            players.iter().map(|p| p.to_string()).collect(),
+            Vec::new(),
+            1,
+            StandardGameType,
+            Some(LeagueOptions {
+                format: Swiss { rounds: 2 },
+                withdrawal_policy: WithdrawalPolicy {
+                    annul_played: false,
+                    drop_remaining: true,
+                },
+                ..Default::default()
+            }),
+        );
+        contract.withdraw_player("Swiss".to_string(), "Bob".to_string());
+        contract.withdraw_player("Swiss".to_string(), "Dave".to_string());
+        play_match(
+            &mut contract,
+            "Swiss",
+            ("Alice", "Charly"),
+            &[FirstPlayerWon],
+        );
+
+        // Alice and Charly already met, so there is no second round
+        assert_eq!(1, swiss_rounds(&contract, "Swiss").len());
+        contract.delete_league("Swiss".to_string(), false);
+    }
+
+    #[test]
+    fn test_withdraw_player_from_swiss() {
+        let _context = create_context();
+
+        let mut contract = LeagueContract::new();
+        let players = ["Alice", "Bob", "Charly", "Dave"];
+        contract.create_league(
+            "Swiss".to_string(),
+            players.iter().map(|p| p.to_string()).collect(),
+            Vec::new(),
+            1,
+            StandardGameType,
+            Some(LeagueOptions {
+                format: Swiss { rounds: 2 },
+                withdrawal_policy: WithdrawalPolicy {
+                    annul_played: false,
+                    drop_remaining: true,
+                },
+                ..Default::default()
+            }),
+        );
+        contract.withdraw_player("Swiss".to_string(), "Dave".to_string());
+        play_match(
+            &mut contract,
+            "Swiss",
+            ("Alice", "Charly"),
+            &[FirstPlayerWon],
+        );
+
+        // Bob and Dave never played, the remaining three are paired with a bye
+        let rounds = swiss_rounds(&contract, "Swiss");
+        assert_eq!(2, rounds.len());
+        assert_eq!(1, rounds[1].0.len());
+        assert!(rounds[1].1.is_some());
+        assert!(rounds[1].0.iter().all(|(a, b)| a != "Dave" && b != "Dave"));
+    }
+
+    #[test]
+    #[should_panic(expected = "Player has withdrawn from the league")]
+    fn test_withdrawn_player_adds_game() {
+        let _context = create_context();
+
+        let mut contract = LeagueContract::new();
+        create_four_player_league(
+            &mut contract,
+            "League",
+            1,
+            LeagueOptions {
+                withdrawal_policy: WithdrawalPolicy {
+                    annul_played: false,
+                    drop_remaining: true,
+                },
+                ..Default::default()
+            },
+        );
+        contract.withdraw_player("League".to_string(), "Dave".to_string());
+        play_match(
+            &mut contract,
+            "League",
+            ("Alice", "Dave"),
+            &[FirstPlayerWon],
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "Only the owner may withdraw players")]
+    fn test_withdraw_player_not_owner() {
+        let mut context = create_context();
+
+        let mut contract = LeagueContract::new();
+        create_four_player_league(&mut contract, "League", 1, LeagueOptions::default());
+
+        testing_env!(context.predecessor_account_id(accounts(2)).build());
+        contract.withdraw_player("League".to_string(), "Dave".to_string());
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "Knockout formats need the remaining matches of a withdrawn player to be forfeited"
+    )]
+    fn test_withdrawal_drop_in_knockout() {
+        let _context = create_context();
+
+        let mut contract = LeagueContract::new();
+        create_four_player_league(
+            &mut contract,
+            "Cup",
+            1,
+            LeagueOptions {
+                format: SingleElimination,
+                withdrawal_policy: WithdrawalPolicy {
+                    annul_played: false,
+                    drop_remaining: true,
+                },
+                ..Default::default()
+            },
+        );
+    }
+
+    #[test]
+    fn test_edit_game_reopens_match() {
+        let _context = create_context();
+
+        let mut contract = LeagueContract::new();
+        create_four_player_league(&mut contract, "League", 3, LeagueOptions::default());
+        play_match(
+            &mut contract,
+            "League",
+            ("Alice", "Bob"),
+            &[FirstPlayerWon, FirstPlayerWon],
+        );
+        assert!(matches!(
+            match_of(&contract, "League", ("Alice", "Bob")),
+            (3, Winner::FirstPlayer)
+        ));
+
+        contract.edit_game(
+            "League".to_string(),
+            ("Alice".to_string(), "Bob".to_string()),
+            0,
+            1,
+            SecondPlayerWon,
+            "{}".to_string(),
+        );
+        assert!(matches!(
+            match_of(&contract, "League", ("Alice", "Bob")),
+            (3, Winner::Undecided)
+        ));
+        play_match(
+            &mut contract,
+            "League",
+            ("Alice", "Bob"),
+            &[SecondPlayerWon],
+        );
+        assert!(matches!(
+            match_of(&contract, "League", ("Alice", "Bob")),
+            (3, Winner::SecondPlayer)
+        ));
+
+        let corrections = contract.get_corrections("League".to_string());
+        assert_eq!(1, corrections.len());
+        assert_eq!(
+            ("Alice", "Bob", 0, 1),
+            (
+                corrections[0].first_player.as_str(),
+                corrections[0].second_player.as_str(),
+                corrections[0].series,
+                corrections[0].game
+            )
+        );
+        assert_eq!(CorrectionKind::Edit, corrections[0].kind);
+        assert!(matches!(corrections[0].original.outcome, FirstPlayerWon));
+        assert_eq!(accounts(0), corrections[0].account);
+    }
+
+    #[test]
+    fn test_edit_game_swapped_names() {
+        let _context = create_context();
+
+        let mut contract = LeagueContract::new();
+        create_four_player_league(&mut contract, "League", 1, LeagueOptions::default());
+        play_match(&mut contract, "League", ("Alice", "Bob"), &[FirstPlayerWon]);
+        contract.edit_game(
+            "League".to_string(),
+            ("Bob".to_string(), "Alice".to_string()),
+            0,
+            0,
+            FirstPlayerWon,
+            "{}".to_string(),
+        );
+        let standings = contract.get_standings("League".to_string());
+        assert_eq!("Bob", standings[0].player);
+        assert_eq!(1, standings[0].matches_won);
+    }
+
+    #[test]
+    #[should_panic(expected = "The match would be decided before its last game")]
+    fn test_edit_game_decided_early() {
+        let _context = create_context();
+
+        let mut contract = LeagueContract::new();
+        create_four_player_league(&mut contract, "League", 3, LeagueOptions::default());
+        play_match(
+            &mut contract,
+            "League",
+            ("Alice", "Bob"),
+            &[FirstPlayerWon, SecondPlayerWon, FirstPlayerWon],
+        );
+        contract.edit_game(
+            "League".to_string(),
+            ("Alice".to_string(), "Bob".to_string()),
+            0,
+            1,
+            FirstPlayerWon,
+            "{}".to_string(),
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "You may not correct games of the league")]
+    fn test_edit_game_untrusted() {
+        let mut context = create_context();
+
+        let mut contract = LeagueContract::new();
+        create_four_player_league(&mut contract, "League", 1, LeagueOptions::default());
+        play_match(&mut contract, "League", ("Alice", "Bob"), &[FirstPlayerWon]);
+
+        testing_env!(context.predecessor_account_id(accounts(2)).build());
+        contract.edit_game(
+            "League".to_string(),
+            ("Alice".to_string(), "Bob".to_string()),
+            0,
+            0,
+            SecondPlayerWon,
+            "{}".to_string(),
+        );
+    }
+
+    #[test]
+    fn test_remove_last_game() {
+        let _context = create_context();
+
+        let mut contract = LeagueContract::new();
+        create_four_player_league(&mut contract, "League", 1, LeagueOptions::default());
+        play_match(&mut contract, "League", ("Alice", "Bob"), &[FirstPlayerWon]);
+        contract.remove_last_game(
+            "League".to_string(),
+            ("Bob".to_string(), "Alice".to_string()),
+            0,
+        );
+
+        // The match is gone as if it was never played
+        assert!(contract.get_league("League".to_string()).matches.is_empty());
+        let corrections = contract.get_corrections("League".to_string());
+        assert_eq!(1, corrections.len());
+        assert_eq!(
+            (CorrectionKind::Removal, 0),
+            (corrections[0].kind, corrections[0].game)
+        );
+        assert!(matches!(corrections[0].original.outcome, FirstPlayerWon));
+    }
+
+    #[test]
+    #[should_panic(expected = "Match not found in the league")]
+    fn test_remove_last_game_unplayed() {
+        let _context = create_context();
+
+        let mut contract = LeagueContract::new();
+        create_four_player_league(&mut contract, "League", 1, LeagueOptions::default());
+        contract.remove_last_game(
+            "League".to_string(),
+            ("Alice".to_string(), "Bob".to_string()),
+            0,
+        );
+    }
+
+    #[test]
+    fn test_edit_game_in_bracket() {
+        let _context = create_context();
+
+        let mut contract = LeagueContract::new();
+        create_cup(&mut contract, "Cup", &["Alice", "Bob", "Charly", "Dave"]);
+        play_match(&mut contract, "Cup", ("Alice", "Dave"), &[FirstPlayerWon]);
+        contract.edit_game(
+            "Cup".to_string(),
+            ("Alice".to_string(), "Dave".to_string()),
+            0,
+            0,
+            SecondPlayerWon,
+            "{}".to_string(),
+        );
+        play_match(&mut contract, "Cup", ("Bob", "Charly"), &[FirstPlayerWon]);
+        play_match(&mut contract, "Cup", ("Dave", "Bob"), &[FirstPlayerWon]);
+        assert_eq!(
+            vec![
+                (Upper, 1, player("Alice"), player("Dave"), player("Dave")),
+                (Upper, 1, player("Bob"), player("Charly"), player("Bob")),
+                (Upper, 2, player("Dave"), player("Bob"), player("Dave")),
+            ],
+            bracket_of(&contract, "Cup")
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "A later match of the bracket depends on this result")]
+    fn test_remove_last_game_bracket_dependency() {
+        let _context = create_context();
+
+        let mut contract = LeagueContract::new();
+        create_cup(&mut contract, "Cup", &["Alice", "Bob", "Charly", "Dave"]);
+        play_match(&mut contract, "Cup", ("Alice", "Dave"), &[FirstPlayerWon]);
+        play_match(&mut contract, "Cup", ("Bob", "Charly"), &[FirstPlayerWon]);
+        play_match(&mut contract, "Cup", ("Alice", "Bob"), &[FirstPlayerWon]);
+        contract.remove_last_game(
+            "Cup".to_string(),
+            ("Alice".to_string(), "Dave".to_string()),
+            0,
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "Matches of earlier swiss rounds have to stay finished")]
+    fn test_remove_last_game_earlier_swiss_round() {
+        let _context = create_context();
+
+        let mut contract = LeagueContract::new();
+        let players = ["Alice", "Bob", "Charly", "Dave"];
+        contract.create_league(
+            "Swiss".to_string(),
+            players.iter().map(|p| p.to_string()).collect(),
+            Vec::new(),
+            1,
+            StandardGameType,
+            Some(LeagueOptions {
+                format: Swiss { rounds: 2 },
+                ..Default::default()
+            }),
+        );
+        play_match(
+            &mut contract,
+            "Swiss",
+            ("Alice", "Charly"),
+            &[FirstPlayerWon],
+        );
+        play_match(&mut contract, "Swiss", ("Bob", "Dave"), &[FirstPlayerWon]);
+        assert_eq!(2, swiss_rounds(&contract, "Swiss").len());
+        contract.remove_last_game(
+            "Swiss".to_string(),
+            ("Alice".to_string(), "Charly".to_string()),
+            0,
+        );
+    }
+
+    #[test]
+    fn test_edit_group_game_before_playoff_match() {
+        let _context = create_context();
+
+        let mut contract = LeagueContract::new();
+        let players = ["Alice", "Bob", "Charly", "Dave", "Eve", "Frank"];
+        create_group_league(&mut contract, "Cup", &players, None);
+        play_match(&mut contract, "Cup", ("Alice", "Dave"), &[FirstPlayerWon]);
+        play_match(&mut contract, "Cup", ("Alice", "Eve"), &[FirstPlayerWon]);
+        play_match(&mut contract, "Cup", ("Eve", "Dave"), &[FirstPlayerWon]);
+        play_match(&mut contract, "Cup", ("Frank", "Bob"), &[FirstPlayerWon]);
+        play_match(&mut contract, "Cup", ("Frank", "Charly"), &[FirstPlayerWon]);
+        play_match(&mut contract, "Cup", ("Bob", "Charly"), &[FirstPlayerWon]);
+
+        // The final group match is corrected, so Charly is seeded instead of Bob
+        contract.edit_game(
+            "Cup".to_string(),
+            ("Bob".to_string(), "Charly".to_string()),
+            0,
+            0,
+            SecondPlayerWon,
+            "{}".to_string(),
+        );
+        assert_eq!(
+            vec![vec!["Alice", "Eve", "Dave"], vec!["Frank", "Charly", "Bob"]],
+            group_tables(&contract, "Cup")
+        );
+        assert!(matches!(
+            contract.get_league("Cup".to_string()).phase,
+            Phase::Playoffs
+        ));
+        assert_eq!(
+            vec![
+                (Upper, 1, player("Alice"), player("Charly"), Pending),
+                (Upper, 1, player("Frank"), player("Eve"), Pending),
+                (Upper, 2, Pending, Pending, Pending),
+            ],
+            bracket_of(&contract, "Cup")
+        );
+
+        // Without its game the group stage is open again
+        contract.remove_last_game(
+            "Cup".to_string(),
+            ("Bob".to_string(), "Charly".to_string()),
+            0,
+        );
+        assert!(contract.get_bracket("Cup".to_string()).is_empty());
+        assert!(matches!(
+            contract.get_league("Cup".to_string()).phase,
+            Phase::Regular
+        ));
+    }
+
+    #[test]
+    #[should_panic(expected = "The group stage cannot be corrected after the playoffs started")]
+    fn test_edit_group_game_after_playoff_match() {
+        let _context = create_context();
+
+        let mut contract = LeagueContract::new();
+        let players = ["Alice", "Bob", "Charly", "Dave"];
+        create_group_league(&mut contract, "Cup", &players, Some(vec![0, 0, 1, 1]));
+        play_match(&mut contract, "Cup", ("Alice", "Bob"), &[FirstPlayerWon]);
+        play_match(&mut contract, "Cup", ("Charly", "Dave"), &[FirstPlayerWon]);
+        play_match(&mut contract, "Cup", ("Alice", "Dave"), &[FirstPlayerWon]);
+        contract.edit_game(
+            "Cup".to_string(),
+            ("Alice".to_string(), "Bob".to_string()),
+            0,
+            0,
+            SecondPlayerWon,
+            "{}".to_string(),
+        );
+    }
+
+    /// Links bob to Alice and charlie to Bob, both by the owner
+    fn link_alice_and_bob(context: &mut VMContextBuilder, contract: &mut LeagueContract) {
+        testing_env!(context.predecessor_account_id(accounts(0)).build());
+        contract.link_account("League".to_string(), "Alice".to_string(), accounts(1));
+        contract.link_account("League".to_string(), "Bob".to_string(), accounts(2));
+    }
+
+    /// Reports a game between Alice and Bob as the account linked to Alice
+    fn report_alice_bob(
+        context: &mut VMContextBuilder,
+        contract: &mut LeagueContract,
+        outcome: GameOutcome,
+    ) {
+        testing_env!(context.predecessor_account_id(accounts(1)).build());
+        contract.report_game(
+            "League".to_string(),
+            ("Alice".to_string(), "Bob".to_string()),
+            outcome,
+            "{}".to_string(),
+        );
+    }
+
+    #[test]
+    fn test_confirm_game_reversed_names() {
+        let mut context = create_context();
+
+        let mut contract = LeagueContract::new();
+        create_double_round_robin(&mut contract, "League");
+        link_alice_and_bob(&mut context, &mut contract);
+        report_alice_bob(&mut context, &mut contract, FirstPlayerWon);
+
+        // Bob names himself first, but the first leg is still the one with the pending game
+        testing_env!(context.predecessor_account_id(accounts(2)).build());
+        contract.confirm_game(
+            "League".to_string(),
+            ("Bob".to_string(), "Alice".to_string()),
+        );
+        let matches = contract.get_league("League".to_string()).matches;
+        assert_eq!(1, matches.len());
+        assert_eq!(
+            ("Alice", Some(1)),
+            (matches[0].first_player.as_str(), matches[0].leg)
+        );
+        assert!(matches!(matches[0].winner, Winner::FirstPlayer));
+    }
+
+    #[test]
+    fn test_reject_game_reversed_names() {
+        let mut context = create_context();
+
+        let mut contract = LeagueContract::new();
+        create_double_round_robin(&mut contract, "League");
+        link_alice_and_bob(&mut context, &mut contract);
+        report_alice_bob(&mut context, &mut contract, FirstPlayerWon);
+
+        testing_env!(context.predecessor_account_id(accounts(2)).build());
+        contract.reject_game(
+            "League".to_string(),
+            ("Bob".to_string(), "Alice".to_string()),
+        );
+        assert!(contract.get_league("League".to_string()).matches.is_empty());
+    }
+
+    #[test]
+    fn test_report_and_confirm_game() {
+        let mut context = create_context();
+
+        let mut contract = LeagueContract::new();
+        create_four_player_league(&mut contract, "League", 1, LeagueOptions::default());
+        link_alice_and_bob(&mut context, &mut contract);
+        assert_eq!(
+            vec![
+                ("Alice".to_string(), accounts(1)),
+                ("Bob".to_string(), accounts(2))
+            ],
+            contract.get_league("League".to_string()).linked_accounts
+        );
+
+        // Bob reports with the names in the other order
+        testing_env!(context.predecessor_account_id(accounts(2)).build());
+        contract.report_game(
+            "League".to_string(),
+            ("Bob".to_string(), "Alice".to_string()),
+            SecondPlayerWon,
+            "{}".to_string(),
+        );
+        let summary = contract.get_league("League".to_string());
+        assert!(matches!(summary.matches[0].winner, Winner::Undecided));
+        assert!(summary.matches[0].games.is_empty());
+        let pending = summary.matches[0].pending.as_ref().unwrap();
+        assert_eq!("Bob", pending.reported_by);
+        assert!(matches!(pending.game.outcome, FirstPlayerWon));
+
+        // The pending game does not count yet
+        let standings = contract.get_standings("League".to_string());
+        assert!(standings.iter().all(|entry| entry.matches_played == 0));
+
+        testing_env!(context.predecessor_account_id(accounts(1)).build());
+        contract.confirm_game(
+            "League".to_string(),
+            ("Alice".to_string(), "Bob".to_string()),
+        );
+        let summary = contract.get_league("League".to_string());
+        assert!(summary.matches[0].pending.is_none());
+        assert!(matches!(summary.matches[0].winner, Winner::FirstPlayer));
+    }
+
+    #[test]
+    #[should_panic(expected = "Only the opponent may confirm the pending game")]
+    fn test_confirm_own_report() {
+        let mut context = create_context();
+
+        let mut contract = LeagueContract::new();
+        create_four_player_league(&mut contract, "League", 1, LeagueOptions::default());
+        link_alice_and_bob(&mut context, &mut contract);
+        report_alice_bob(&mut context, &mut contract, FirstPlayerWon);
+        contract.confirm_game(
+            "League".to_string(),
+            ("Alice".to_string(), "Bob".to_string()),
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "You may only report games of your own matches")]
+    fn test_report_game_of_others() {
+        let mut context = create_context();
+
+        let mut contract = LeagueContract::new();
+        create_four_player_league(&mut contract, "League", 1, LeagueOptions::default());
+        link_alice_and_bob(&mut context, &mut contract);
+        testing_env!(context.predecessor_account_id(accounts(1)).build());
+        contract.report_game(
+            "League".to_string(),
+            ("Bob".to_string(), "Charly".to_string()),
+            FirstPlayerWon,
+            "{}".to_string(),
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "A game of the match is already pending")]
+    fn test_report_game_twice() {
+        let mut context = create_context();
+
+        let mut contract = LeagueContract::new();
+        create_four_player_league(&mut contract, "League", 3, LeagueOptions::default());
+        link_alice_and_bob(&mut context, &mut contract);
+        report_alice_bob(&mut context, &mut contract, FirstPlayerWon);
+        report_alice_bob(&mut context, &mut contract, FirstPlayerWon);
+    }
+
+    #[test]
+    fn test_add_game_overrides_pending() {
+        let mut context = create_context();
+
+        let mut contract = LeagueContract::new();
+        create_four_player_league(&mut contract, "League", 1, LeagueOptions::default());
+        link_alice_and_bob(&mut context, &mut contract);
+        report_alice_bob(&mut context, &mut contract, FirstPlayerWon);
+
+        testing_env!(context.predecessor_account_id(accounts(0)).build());
+        play_match(
+            &mut contract,
+            "League",
+            ("Alice", "Bob"),
+            &[SecondPlayerWon],
+        );
+        let summary = contract.get_league("League".to_string());
+        assert!(summary.matches[0].pending.is_none());
+        assert!(matches!(summary.matches[0].winner, Winner::SecondPlayer));
+    }
+
+    #[test]
+    fn test_reject_game() {
+        let mut context = create_context();
+
+        let mut contract = LeagueContract::new();
+        create_four_player_league(&mut contract, "League", 1, LeagueOptions::default());
+        link_alice_and_bob(&mut context, &mut contract);
+        report_alice_bob(&mut context, &mut contract, FirstPlayerWon);
+
+        testing_env!(context.predecessor_account_id(accounts(2)).build());
+        contract.reject_game(
+            "League".to_string(),
+            ("Alice".to_string(), "Bob".to_string()),
+        );
+        assert!(contract.get_league("League".to_string()).matches.is_empty());
+    }
+
+    #[test]
+    fn test_link_account_request() {
+        let mut context = create_context();
+
+        let mut contract = LeagueContract::new();
+        create_four_player_league(&mut contract, "League", 1, LeagueOptions::default());
+        testing_env!(context.predecessor_account_id(accounts(1)).build());
+        contract.link_account("League".to_string(), "Alice".to_string(), accounts(1));
+        assert_eq!(
+            "bob asked to be linked to Alice in league League",
+            get_logs()[0]
+        );
+
+        // Bob has no account yet, but Alice's second account is only a request
+        testing_env!(context.predecessor_account_id(accounts(3)).build());
+        contract.link_account("League".to_string(), "Bob".to_string(), accounts(3));
+        let summary = contract.get_league("League".to_string());
+        assert!(summary.linked_accounts.is_empty());
+        assert_eq!(
+            vec![
+                ("Alice".to_string(), accounts(1)),
+                ("Bob".to_string(), accounts(3))
+            ],
+            summary.link_requests
+        );
+
+        // The owner links Alice's request and Bob's real account
+        testing_env!(context.predecessor_account_id(accounts(0)).build());
+        contract.link_account("League".to_string(), "Alice".to_string(), accounts(1));
+        contract.link_account("League".to_string(), "Bob".to_string(), accounts(2));
+        let summary = contract.get_league("League".to_string());
+        assert_eq!(
+            vec![
+                ("Alice".to_string(), accounts(1)),
+                ("Bob".to_string(), accounts(2))
+            ],
+            summary.linked_accounts
+        );
+        assert!(summary.link_requests.is_empty());
+    }
+
+    #[test]
+    #[should_panic(expected = "You may only ask to link your own account")]
+    fn test_link_account_of_other() {
+        let mut context = create_context();
+
+        let mut contract = LeagueContract::new();
+        create_four_player_league(&mut contract, "League", 1, LeagueOptions::default());
+        testing_env!(context.predecessor_account_id(accounts(3)).build());
+        contract.link_account("League".to_string(), "Bob".to_string(), accounts(2));
+    }
+
+    #[test]
+    #[should_panic(expected = "Player is already linked to an account")]
+    fn test_link_account_taken() {
+        let mut context = create_context();
+
+        let mut contract = LeagueContract::new();
+        create_four_player_league(&mut contract, "League", 1, LeagueOptions::default());
+        link_alice_and_bob(&mut context, &mut contract);
+        testing_env!(context.predecessor_account_id(accounts(3)).build());
+        contract.link_account("League".to_string(), "Alice".to_string(), accounts(3));
+    }
+
+    #[test]
+    #[should_panic(expected = "Account is already linked to another player")]
+    fn test_link_account_twice() {
+        let _context = create_context();
+
+        let mut contract = LeagueContract::new();
+        create_four_player_league(&mut contract, "League", 1, LeagueOptions::default());
+        contract.link_account("League".to_string(), "Alice".to_string(), accounts(1));
+        contract.link_account("League".to_string(), "Bob".to_string(), accounts(1));
+    }
+
+    #[test]
+    #[should_panic(expected = "Match is frozen by an open dispute")]
+    fn test_dispute_freezes_match() {
+        let _context = create_context();
+
+        let mut contract = LeagueContract::new();
+        create_four_player_league(&mut contract, "League", 3, LeagueOptions::default());
+        play_match(&mut contract, "League", ("Alice", "Bob"), &[FirstPlayerWon]);
+        contract.open_dispute(
+            "League".to_string(),
+            ("Alice".to_string(), "Bob".to_string()),
+            0,
+            Some(0),
+            "Wrong map".to_string(),
+            None,
+        );
+        play_match(&mut contract, "League", ("Alice", "Bob"), &[FirstPlayerWon]);
+    }
+
+    #[test]
+    fn test_dispute_overturn_game() {
+        let mut context = create_context();
+
+        let mut contract = LeagueContract::new();
+        create_four_player_league(&mut contract, "League", 1, LeagueOptions::default());
+        link_alice_and_bob(&mut context, &mut contract);
+        testing_env!(context.predecessor_account_id(accounts(0)).build());
+        play_match(&mut contract, "League", ("Alice", "Bob"), &[FirstPlayerWon]);
+
+        testing_env!(context.predecessor_account_id(accounts(2)).build());
+        let id = contract.open_dispute(
+            "League".to_string(),
+            ("Bob".to_string(), "Alice".to_string()),
+            0,
+            Some(0),
+            "Alice disconnected and lost".to_string(),
+            Some("https://example.org/replay".to_string()),
+        );
+        let disputes = contract.get_disputes("League".to_string());
+        assert_eq!((0, 1), (id, disputes.len()));
+        assert_eq!(accounts(2), disputes[0].opened_by);
+        assert!(disputes[0].resolution.is_none());
+
+        testing_env!(context.predecessor_account_id(accounts(0)).build());
+        contract.resolve_dispute("League".to_string(), id, DisputeRuling::Overturn);
+        assert!(matches!(
+            match_of(&contract, "League", ("Alice", "Bob")),
+            (1, Winner::SecondPlayer)
+        ));
+
+        let disputes = contract.get_disputes("League".to_string());
+        let resolution = disputes[0].resolution.as_ref().unwrap();
+        assert_eq!(
+            (DisputeRuling::Overturn, accounts(0)),
+            (resolution.ruling, resolution.resolved_by.clone())
+        );
+        let corrections = contract.get_corrections("League".to_string());
+        assert_eq!(1, corrections.len());
+        assert_eq!(CorrectionKind::Edit, corrections[0].kind);
+    }
+
+    #[test]
+    fn test_dispute_overturn_decides_early() {
+        let _context = create_context();
+
+        let mut contract = LeagueContract::new();
+        create_four_player_league(&mut contract, "League", 3, LeagueOptions::default());
+        play_match(
+            &mut contract,
+            "League",
+            ("Alice", "Bob"),
+            &[FirstPlayerWon, SecondPlayerWon, FirstPlayerWon],
+        );
+        let id = contract.open_dispute(
+            "League".to_string(),
+            ("Alice".to_string(), "Bob".to_string()),
+            0,
+            Some(0),
+            "Alice disconnected and lost".to_string(),
+            None,
+        );
+        // Bob wins the first two games now, so the last game is not needed anymore
+        contract.resolve_dispute("League".to_string(), id, DisputeRuling::Overturn);
+        assert!(matches!(
+            match_of(&contract, "League", ("Alice", "Bob")),
+            (3, Winner::SecondPlayer)
+        ));
+
+        let corrections = contract.get_corrections("League".to_string());
+        assert_eq!(2, corrections.len());
+        assert_eq!(
+            (CorrectionKind::Edit, 0),
+            (corrections[0].kind, corrections[0].game)
+        );
+        assert_eq!(
+            (CorrectionKind::Removal, 2),
+            (corrections[1].kind, corrections[1].game)
+        );
+    }
+
+    #[test]
+    fn test_dispute_replay_forfeit() {
+        let _context = create_context();
+
+        let mut contract = LeagueContract::new();
+        create_four_player_league(&mut contract, "League", 1, LeagueOptions::default());
+        contract.record_forfeit(
+            "League".to_string(),
+            ("Alice".to_string(), "Bob".to_string()),
+            Some("Alice".to_string()),
+            "Bob did not show up".to_string(),
+        );
+        let id = contract.open_dispute(
+            "League".to_string(),
+            ("Alice".to_string(), "Bob".to_string()),
+            0,
+            None,
+            "Bob was there on time".to_string(),
+            None,
+        );
+        contract.resolve_dispute("League".to_string(), id, DisputeRuling::Replay);
+        assert!(contract.get_league("League".to_string()).matches.is_empty());
+
+        play_match(
+            &mut contract,
+            "League",
+            ("Alice", "Bob"),
+            &[SecondPlayerWon],
+        );
+        assert!(matches!(
+            match_of(&contract, "League", ("Alice", "Bob")),
+            (1, Winner::SecondPlayer)
+        ));
+    }
+
+    #[test]
+    fn test_dispute_uphold() {
+        let _context = create_context();
+
+        let mut contract = LeagueContract::new();
+        create_four_player_league(&mut contract, "League", 3, LeagueOptions::default());
+        play_match(&mut contract, "League", ("Alice", "Bob"), &[FirstPlayerWon]);
+        let id = contract.open_dispute(
+            "League".to_string(),
+            ("Alice".to_string(), "Bob".to_string()),
+            0,
+            Some(0),
+            "Wrong map".to_string(),
+            None,
+        );
+        contract.resolve_dispute("League".to_string(), id, DisputeRuling::Uphold);
+
+        // The match is not frozen anymore and the result stands
+        play_match(&mut contract, "League", ("Alice", "Bob"), &[FirstPlayerWon]);
+        assert!(matches!(
+            match_of(&contract, "League", ("Alice", "Bob")),
+            (3, Winner::FirstPlayer)
+        ));
+        assert!(contract.get_corrections("League".to_string()).is_empty());
+    }
+
+    #[test]
+    #[should_panic(expected = "Dispute is already resolved")]
+    fn test_resolve_dispute_twice() {
+        let _context = create_context();
+
+        let mut contract = LeagueContract::new();
+        create_four_player_league(&mut contract, "League", 1, LeagueOptions::default());
+        play_match(&mut contract, "League", ("Alice", "Bob"), &[FirstPlayerWon]);
+        let id = contract.open_dispute(
+            "League".to_string(),
+            ("Alice".to_string(), "Bob".to_string()),
+            0,
+            None,
+            "Wrong map".to_string(),
+            None,
+        );
+        contract.resolve_dispute("League".to_string(), id, DisputeRuling::Uphold);
+        contract.resolve_dispute("League".to_string(), id, DisputeRuling::Overturn);
+    }
+
+    #[test]
+    #[should_panic(expected = "You may not resolve disputes in the league")]
+    fn test_resolve_dispute_as_player() {
+        let mut context = create_context();
+
+        let mut contract = LeagueContract::new();
+        create_four_player_league(&mut contract, "League", 1, LeagueOptions::default());
+        link_alice_and_bob(&mut context, &mut contract);
+        testing_env!(context.predecessor_account_id(accounts(0)).build());
+        play_match(&mut contract, "League", ("Alice", "Bob"), &[FirstPlayerWon]);
+
+        testing_env!(context.predecessor_account_id(accounts(2)).build());
+        let id = contract.open_dispute(
+            "League".to_string(),
+            ("Alice".to_string(), "Bob".to_string()),
+            0,
+            Some(0),
+            "Alice disconnected and lost".to_string(),
+            None,
+        );
+        contract.resolve_dispute("League".to_string(), id, DisputeRuling::Overturn);
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "Only the players of the match and trusted accounts may open a dispute"
+    )]
+    fn test_open_dispute_unlinked() {
+        let mut context = create_context();
+
+        let mut contract = LeagueContract::new();
+        create_four_player_league(&mut contract, "League", 1, LeagueOptions::default());
+        play_match(&mut contract, "League", ("Alice", "Bob"), &[FirstPlayerWon]);
+
+        testing_env!(context.predecessor_account_id(accounts(3)).build());
+        contract.open_dispute(
+            "League".to_string(),
+            ("Alice".to_string(), "Bob".to_string()),
+            0,
+            None,
+            "Looks wrong".to_string(),
+            None,
+        );
+    }
+
+    /// Creates a league in registration without any players
+    fn create_registration_league(
+        contract: &mut LeagueContract,
+        name: &str,
+        registration: Registration,
+        format: LeagueFormat,
+    ) {
+        contract.create_league(
+            name.to_string(),
+            Vec::new(),
+            Vec::new(),
+            1,
+            StandardGameType,
+            Some(LeagueOptions {
+                format,
+                registration: Some(registration),
+                ..Default::default()
+            }),
+        );
+    }
+
+    /// Lets the accounts 1, 2, 3, ... join the league with the `names` in order
+    fn join_all(context: &mut VMContextBuilder, contract: &mut LeagueContract, names: &[&str]) {
+        for (index, name) in names.iter().enumerate() {
+            testing_env!(context.predecessor_account_id(accounts(index + 1)).build());
+            contract.join_league("League".to_string(), name.to_string());
+        }
+        testing_env!(context.predecessor_account_id(accounts(0)).build());
+    }
+
+    #[test]
+    fn test_registration_and_start() {
+        let mut context = create_context();
+
+        let mut contract = LeagueContract::new();
+        create_registration_league(
+            &mut contract,
+            "League",
+            Registration {
+                cap: Some(4),
+                deadline: None,
+            },
+            LeagueFormat::RoundRobin,
+        );
+        join_all(
+            &mut context,
+            &mut contract,
+            &["Alice", "Bob", "Charly", "Dave"],
+        );
+        let summary = contract.get_league("League".to_string());
+        assert_eq!(Lifecycle::Registration, summary.lifecycle);
+        assert_eq!(Some(4), summary.registration.unwrap().cap);
+        assert_eq!(4, summary.players.len());
+        assert!(contract.get_schedule("League".to_string()).is_empty());
+
+        // Charly leaves, so Dave moves up and keeps the linked account
+        contract.remove_entry("League".to_string(), "Charly".to_string());
+        testing_env!(context.predecessor_account_id(accounts(5)).build());
+        contract.join_league("League".to_string(), "Eve".to_string());
+        testing_env!(context.predecessor_account_id(accounts(0)).build());
+
+        contract.start_league("League".to_string(), None);
+        let summary = contract.get_league("League".to_string());
+        assert_eq!(Lifecycle::Running, summary.lifecycle);
+        assert_eq!(vec!["Alice", "Bob", "Dave", "Eve"], summary.players);
+        assert_eq!(
+            vec![
+                ("Alice".to_string(), accounts(1)),
+                ("Bob".to_string(), accounts(2)),
+                ("Dave".to_string(), accounts(4)),
+                ("Eve".to_string(), accounts(5)),
+            ],
+            summary.linked_accounts
+        );
+        assert_eq!(3, contract.get_schedule("League".to_string()).len());
+
+        // The joined players report their own games
+        testing_env!(context.predecessor_account_id(accounts(4)).build());
+        contract.report_game(
+            "League".to_string(),
+            ("Dave".to_string(), "Eve".to_string()),
+            FirstPlayerWon,
+            "{}".to_string(),
+        );
+        testing_env!(context.predecessor_account_id(accounts(5)).build());
+        contract.confirm_game(
+            "League".to_string(),
+            ("Dave".to_string(), "Eve".to_string()),
+        );
+        assert!(matches!(
+            match_of(&contract, "League", ("Dave", "Eve")),
+            (1, Winner::FirstPlayer)
+        ));
+    }
+
+    #[test]
+    fn test_registration_swiss() {
+        let mut context = create_context();
+
+        let mut contract = LeagueContract::new();
+        create_registration_league(
+            &mut contract,
+            "League",
+            Registration::default(),
+            Swiss { rounds: 2 },
+        );
+        join_all(
+            &mut context,
+            &mut contract,
+            &["Alice", "Bob", "Charly", "Dave"],
+        );
+        assert!(swiss_rounds(&contract, "League").is_empty());
+        contract.start_league("League".to_string(), None);
+        assert_eq!(1, swiss_rounds(&contract, "League").len());
+    }
+
+    #[test]
+    #[should_panic(expected = "League has not started yet")]
+    fn test_add_game_in_registration() {
+        let mut context = create_context();
+
+        let mut contract = LeagueContract::new();
+        create_registration_league(
+            &mut contract,
+            "League",
+            Registration::default(),
+            LeagueFormat::RoundRobin,
+        );
+        join_all(&mut context, &mut contract, &["Alice", "Bob", "Charly"]);
+        play_match(&mut contract, "League", ("Alice", "Bob"), &[FirstPlayerWon]);
+    }
+
+    #[test]
+    #[should_panic(expected = "League is full")]
+    fn test_join_beyond_max_players() {
+        let mut context = create_context();
+
+        let mut contract = LeagueContract::new();
+        let players = (0..MAX_PLAYERS as usize)
+            .map(|index| format!("Player {}", index))
+            .collect();
+        contract.create_league(
+            "League".to_string(),
+            players,
+            Vec::new(),
+            1,
+            StandardGameType,
+            Some(LeagueOptions {
+                registration: Some(Registration {
+                    cap: Some(200),
+                    deadline: None,
+                }),
+                ..Default::default()
+            }),
+        );
+        join_all(&mut context, &mut contract, &["Alice"]);
+    }
+
+    #[test]
+    #[should_panic(expected = "League is full")]
+    fn test_join_full_league() {
+        let mut context = create_context();
+
+        let mut contract = LeagueContract::new();
+        create_registration_league(
+            &mut contract,
+            "League",
+            Registration {
+                cap: Some(3),
+                deadline: None,
+            },
+            LeagueFormat::RoundRobin,
+        );
+        join_all(
+            &mut context,
+            &mut contract,
+            &["Alice", "Bob", "Charly", "Dave"],
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "Registration deadline has passed")]
+    fn test_join_after_deadline() {
+        let mut context = create_context();
+
+        let mut contract = LeagueContract::new();
+        create_registration_league(
+            &mut contract,
+            "League",
+            Registration {
+                cap: None,
+                deadline: Some(U64(1000)),
+            },
+            LeagueFormat::RoundRobin,
+        );
+        testing_env!(context.block_timestamp(2000).build());
+        join_all(&mut context, &mut contract, &["Alice"]);
+    }
+
+    #[test]
+    #[should_panic(expected = "Registration is closed")]
+    fn test_join_after_start() {
         let mut context = create_context();
 
         let mut contract = LeagueContract::new();
-        let name = "SomeLeague".to_string();
-        let players = vec!["Alice".to_string(), "Bob".to_string(), "Charly".to_string()];
-        let accs = vec![accounts(1)];
-        contract.create_league(name.clone(), players.clone(), accs, 3, StandardGameType);
+        create_registration_league(
+            &mut contract,
+            "League",
+            Registration::default(),
+            LeagueFormat::RoundRobin,
+        );
+        join_all(&mut context, &mut contract, &["Alice", "Bob", "Charly"]);
+        contract.start_league("League".to_string(), None);
+        testing_env!(context.predecessor_account_id(accounts(4)).build());
+        contract.join_league("League".to_string(), "Dave".to_string());
+    }
 
-        context.predecessor_account_id(accounts(1));
-        testing_env!(context.build());
+    #[test]
+    #[should_panic(expected = "Account is already registered")]
+    fn test_join_twice() {
+        let mut context = create_context();
 
-        contract.add_game(
-            name,
-            ("Malory".to_string(), players[1].clone()),
-            true,
-            "{}".to_string(),
+        let mut contract = LeagueContract::new();
+        create_registration_league(
+            &mut contract,
+            "League",
+            Registration::default(),
+            LeagueFormat::RoundRobin,
         );
+        testing_env!(context.predecessor_account_id(accounts(1)).build());
+        contract.join_league("League".to_string(), "Alice".to_string());
+        contract.join_league("League".to_string(), "Alicia".to_string());
     }
 
     #[test]
-    #[should_panic(expected = "Need different players")]
-    fn test_add_game_with_player_twice() {
+    #[should_panic(expected = "League needs at least 3 participant")]
+    fn test_start_too_few_players() {
         let mut context = create_context();
 
         let mut contract = LeagueContract::new();
-        let name = "SomeLeague".to_string();
-        let players = vec!["Alice".to_string(), "Bob".to_string(), "Charly".to_string()];
-        let accs = vec![accounts(1)];
-        contract.create_league(name.clone(), players.clone(), accs, 3, StandardGameType);
+        create_registration_league(
+            &mut contract,
+            "League",
+            Registration::default(),
+            LeagueFormat::RoundRobin,
+        );
+        join_all(&mut context, &mut contract, &["Alice", "Bob"]);
+        contract.start_league("League".to_string(), None);
+    }
 
-        context.predecessor_account_id(accounts(1));
-        testing_env!(context.build());
+    #[test]
+    #[should_panic(expected = "Only the owner may start the league")]
+    fn test_start_not_owner() {
+        let mut context = create_context();
 
-        contract.add_game(
-            name,
-            (players[1].clone(), players[1].clone()),
-            true,
-            "{}".to_string(),
+        let mut contract = LeagueContract::new();
+        create_registration_league(
+            &mut contract,
+            "League",
+            Registration::default(),
+            LeagueFormat::RoundRobin,
         );
+        join_all(&mut context, &mut contract, &["Alice", "Bob", "Charly"]);
+        testing_env!(context.predecessor_account_id(accounts(1)).build());
+        contract.start_league("League".to_string(), None);
     }
 
     #[test]
-    fn test_delete_finished_match() {
-        // To lazy to define a new predecessor here
+    fn test_pause_and_resume() {
         let _context = create_context();
 
         let mut contract = LeagueContract::new();
-        let name = "SomeLeague".to_string();
-        let players = vec!["Alice".to_string(), "Bob".to_string(), "Charly".to_string()];
-        contract.create_league(
-            name.clone(),
-            players.clone(),
-            Vec::new(),
-            3,
-            StandardGameType,
+        create_four_player_league(&mut contract, "League", 1, LeagueOptions::default());
+        play_match(&mut contract, "League", ("Alice", "Bob"), &[FirstPlayerWon]);
+        contract.pause_league("League".to_string());
+        assert_eq!(
+            Lifecycle::Paused,
+            contract.get_league("League".to_string()).lifecycle
         );
 
-        contract.add_game(
-            name.clone(),
-            (players[0].clone(), players[1].clone()),
-            true,
-            "{}".to_string(),
-        );
-        contract.add_game(
-            name.clone(),
-            (players[0].clone(), players[1].clone()),
-            true,
+        // Trusted accounts may still correct games while the league is paused
+        contract.edit_game(
+            "League".to_string(),
+            ("Alice".to_string(), "Bob".to_string()),
+            0,
+            0,
+            SecondPlayerWon,
             "{}".to_string(),
         );
 
-        contract.add_game(
-            name.clone(),
-            (players[0].clone(), players[2].clone()),
-            false,
-            "{}".to_string(),
+        contract.resume_league("League".to_string());
+        play_match(
+            &mut contract,
+            "League",
+            ("Alice", "Charly"),
+            &[FirstPlayerWon],
         );
-        contract.add_game(
-            name.clone(),
-            (players[0].clone(), players[2].clone()),
-            false,
-            "{}".to_string(),
+        assert_eq!(
+            Lifecycle::Running,
+            contract.get_league("League".to_string()).lifecycle
         );
+        assert_eq!(2, contract.get_league("League".to_string()).matches.len());
+    }
 
-        contract.add_game(
-            name.clone(),
-            (players[1].clone(), players[2].clone()),
-            true,
-            "{}".to_string(),
-        );
-        contract.add_game(
-            name.clone(),
-            (players[1].clone(), players[2].clone()),
-            false,
-            "{}".to_string(),
-        );
-        contract.add_game(
-            name.clone(),
-            (players[1].clone(), players[2].clone()),
-            true,
-            "{}".to_string(),
-        );
+    #[test]
+    #[should_panic(expected = "League is paused")]
+    fn test_add_game_paused() {
+        let _context = create_context();
 
-        // None forced of course
-        contract.delete_league(name, false);
+        let mut contract = LeagueContract::new();
+        create_four_player_league(&mut contract, "League", 1, LeagueOptions::default());
+        contract.pause_league("League".to_string());
+        play_match(&mut contract, "League", ("Alice", "Bob"), &[FirstPlayerWon]);
     }
 
     #[test]
-    fn test_simpler_league() {
-        // To lazy to define a new predecessor here
+    #[should_panic(expected = "League is paused")]
+    fn test_withdraw_player_paused() {
         let _context = create_context();
 
         let mut contract = LeagueContract::new();
-        let name = "SomeLeague".to_string();
-        let players = vec!["Alice".to_string(), "Bob".to_string(), "Charly".to_string()];
-        contract.create_league(
-            name.clone(),
-            players.clone(),
-            Vec::new(),
-            1,
-            StandardGameType,
-        );
+        create_four_player_league(&mut contract, "League", 1, LeagueOptions::default());
+        contract.pause_league("League".to_string());
+        contract.withdraw_player("League".to_string(), "Dave".to_string());
+    }
 
-        contract.add_game(
-            name.clone(),
-            (players[0].clone(), players[1].clone()),
-            true,
-            "{}".to_string(),
-        );
-        contract.add_game(
-            name.clone(),
-            (players[0].clone(), players[2].clone()),
-            false,
-            "{}".to_string(),
-        );
-        contract.add_game(
-            name.clone(),
-            (players[1].clone(), players[2].clone()),
-            true,
-            "{}".to_string(),
-        );
+    #[test]
+    #[should_panic(expected = "League is closed")]
+    fn test_add_game_closed() {
+        let _context = create_context();
 
-        // None forced of course
-        contract.delete_league(name, false);
+        let mut contract = LeagueContract::new();
+        create_four_player_league(&mut contract, "League", 1, LeagueOptions::default());
+        contract.close_league("League".to_string(), true);
+        play_match(&mut contract, "League", ("Alice", "Bob"), &[FirstPlayerWon]);
     }
 
     #[test]
-    fn test_complex_league() {
-        // To lazy to define a new predecessor here
+    #[should_panic(expected = "League is closed")]
+    fn test_edit_game_closed() {
         let _context = create_context();
 
         let mut contract = LeagueContract::new();
-        let name = "SomeLeague".to_string();
-        let players = vec!["Alice".to_string(), "Bob".to_string(), "Charly".to_string()];
-        contract.create_league(
-            name.clone(),
-            players.clone(),
-            Vec::new(),
-            5,
-            StandardGameType,
-        );
-
-        contract.add_game(
-            name.clone(),
-            (players[0].clone(), players[1].clone()),
-            true,
-            "{}".to_string(),
-        );
-        contract.add_game(
-            name.clone(),
-            (players[0].clone(), players[1].clone()),
-            false,
-            "{}".to_string(),
-        );
-        contract.add_game(
-            name.clone(),
-            (players[0].clone(), players[1].clone()),
-            false,
-            "{}".to_string(),
-        );
-        contract.add_game(
-            name.clone(),
-            (players[0].clone(), players[1].clone()),
-            true,
-            "{}".to_string(),
-        );
-        contract.add_game(
-            name,
-            (players[0].clone(), players[1].clone()),
-            true,
+        create_four_player_league(&mut contract, "League", 1, LeagueOptions::default());
+        play_match(&mut contract, "League", ("Alice", "Bob"), &[FirstPlayerWon]);
+        contract.close_league("League".to_string(), true);
+        contract.edit_game(
+            "League".to_string(),
+            ("Alice".to_string(), "Bob".to_string()),
+            0,
+            0,
+            SecondPlayerWon,
             "{}".to_string(),
         );
     }
 
     #[test]
-    #[should_panic(expected = "Match is already finished")]
-    fn test_complex_league_failing() {
-        // To lazy to define a new predecessor here
+    #[should_panic(expected = "League is not finished yet")]
+    fn test_close_unfinished_league() {
         let _context = create_context();
 
         let mut contract = LeagueContract::new();
-        let name = "SomeLeague".to_string();
-        let players = vec!["Alice".to_string(), "Bob".to_string(), "Charly".to_string()];
-        contract.create_league(
-            name.clone(),
-            players.clone(),
-            Vec::new(),
-            5,
-            StandardGameType,
-        );
-
-        contract.add_game(
-            name.clone(),
-            (players[0].clone(), players[1].clone()),
-            true,
-            "{}".to_string(),
-        );
-        contract.add_game(
-            name.clone(),
-            (players[0].clone(), players[1].clone()),
-            false,
-            "{}".to_string(),
-        );
-        contract.add_game(
-            name.clone(),
-            (players[0].clone(), players[1].clone()),
-            false,
-            "{}".to_string(),
-        );
-        contract.add_game(
-            name.clone(),
-            (players[0].clone(), players[1].clone()),
-            true,
-            "{}".to_string(),
-        );
-        contract.add_game(
-            name.clone(),
-            (players[0].clone(), players[1].clone()),
-            true,
-            "{}".to_string(),
-        );
-        contract.add_game(
-            name,
-            (players[0].clone(), players[1].clone()),
-            false,
-            "{}".to_string(),
-        );
+        create_four_player_league(&mut contract, "League", 1, LeagueOptions::default());
+        contract.close_league("League".to_string(), false);
     }
 
-    /// The idea of the macro is to to it only once and not add the game types everywhere
-    /// but for the beginning it is nice to test whether the view works
-    /// Or well maybe nice to check here if every game is inside
     #[test]
-    fn test_list_game_types() {
+    #[should_panic(expected = "Open disputes have to be resolved first")]
+    fn test_close_with_open_dispute() {
         let _context = create_context();
 
-        let contract = LeagueContract::new();
-        assert_eq!(vec!("StandardGameType"), contract.get_game_types());
+        let mut contract = LeagueContract::new();
+        create_four_player_league(&mut contract, "League", 1, LeagueOptions::default());
+        play_match(&mut contract, "League", ("Alice", "Bob"), &[FirstPlayerWon]);
+        contract.open_dispute(
+            "League".to_string(),
+            ("Alice".to_string(), "Bob".to_string()),
+            0,
+            None,
+            "Wrong map".to_string(),
+            None,
+        );
+        contract.close_league("League".to_string(), true);
     }
 
     #[test]
-    fn test_standard_game_structure() {
+    fn test_archive_and_delete() {
         let _context = create_context();
 
-        let contract = LeagueContract::new();
+        let mut contract = LeagueContract::new();
+        create_four_player_league(&mut contract, "League", 1, LeagueOptions::default());
+        contract.close_league("League".to_string(), true);
+        contract.archive_league("League".to_string());
         assert_eq!(
-            "".to_string(),
-            contract.get_game_structure(GameType::StandardGameType)
+            Lifecycle::Archived,
+            contract.get_league("League".to_string()).lifecycle
         );
+
+        // A closed league counts as finished
+        contract.delete_league("League".to_string(), false);
     }
 
-    /// Kept it simple as the json gets quite annoying
     #[test]
-    fn test_league_output() {
+    #[should_panic(expected = "League is archived")]
+    fn test_trusted_account_archived() {
         let _context = create_context();
 
         let mut contract = LeagueContract::new();
-        let name = "SomeLeague".to_string();
-        let players = vec!["Alice".to_string(), "Bob".to_string(), "Charly".to_string()];
+        create_four_player_league(&mut contract, "League", 1, LeagueOptions::default());
+        contract.close_league("League".to_string(), true);
+        contract.archive_league("League".to_string());
+        contract.add_trusted_account("League".to_string(), accounts(3));
+    }
+
+    #[test]
+    #[should_panic(expected = "League cannot go from Running to Archived")]
+    fn test_archive_running_league() {
+        let _context = create_context();
+
+        let mut contract = LeagueContract::new();
+        create_four_player_league(&mut contract, "League", 1, LeagueOptions::default());
+        contract.archive_league("League".to_string());
+    }
+
+    #[test]
+    #[should_panic(expected = "Only the owner may change the state of the league")]
+    fn test_pause_not_owner() {
+        let mut context = create_context();
+
+        let mut contract = LeagueContract::new();
+        create_four_player_league(&mut contract, "League", 1, LeagueOptions::default());
+        testing_env!(context.predecessor_account_id(accounts(2)).build());
+        contract.pause_league("League".to_string());
+    }
+
+    #[test]
+    fn test_draft_league() {
+        let mut context = create_context();
+
+        let mut contract = LeagueContract::new();
         contract.create_league(
-            name.clone(),
-            players.clone(),
+            "League".to_string(),
             Vec::new(),
-            5,
+            Vec::new(),
+            1,
             StandardGameType,
+            Some(LeagueOptions {
+                registration: Some(Registration::default()),
+                draft: true,
+                ..Default::default()
+            }),
         );
-
-        contract.add_game(
-            name,
-            (players[0].clone(), players[1].clone()),
-            true,
-            "{}".to_string(),
+        assert_eq!(
+            Lifecycle::Draft,
+            contract.get_league("League".to_string()).lifecycle
+        );
+        contract.open_registration("League".to_string());
+        join_all(&mut context, &mut contract, &["Alice", "Bob", "Charly"]);
+        contract.start_league("League".to_string(), None);
+        assert_eq!(
+            Lifecycle::Running,
+            contract.get_league("League".to_string()).lifecycle
         );
+    }
 
-        let summary = contract.get_league("SomeLeague".to_string());
-        assert_eq!("{\"best_of\":5,\"matches\":[{\"first_player\":\"Alice\",\"second_player\":\"Bob\",\"winner\":\"None\",\"games\":[{\"first_player_is_winner\":true,\"data\":\"{}\"}]}]}".to_string(), summary);
-        println!("{}", summary);
+    #[test]
+    #[should_panic(expected = "Registration is closed")]
+    fn test_join_draft_league() {
+        let mut context = create_context();
+
+        let mut contract = LeagueContract::new();
+        contract.create_league(
+            "League".to_string(),
+            Vec::new(),
+            Vec::new(),
+            1,
+            StandardGameType,
+            Some(LeagueOptions {
+                registration: Some(Registration::default()),
+                draft: true,
+                ..Default::default()
+            }),
+        );
+        join_all(&mut context, &mut contract, &["Alice"]);
     }
 }
@@ -0,0 +1,359 @@
+//! Knockout brackets of a league
+//!
+//! A bracket is a list of matches where each side is filled by a seed, a bye
+//! or the winner or loser of an earlier match.
+//! The bracket itself is never stored. It is generated from the number of players
+//! and filled with the results of the game matches whenever it is needed.
+//! This way the game matches stay the only state of the league.
+
+use serde::Serialize;
+use std::collections::HashMap;
+
+/// Where a side of a `BracketMatch` comes from
+#[derive(Clone, Copy, PartialEq)]
+pub enum Slot {
+    /// The seed with the given index, i.e. 0 is the top seed
+    Seed(u8),
+    /// Nobody, the other side advances without playing
+    Bye,
+    /// The winner of the bracket match with the given index
+    WinnerOf(usize),
+    /// The loser of the bracket match with the given index
+    LoserOf(usize),
+    /// The first side of the bracket match with the given index if it lost, otherwise a bye
+    ///
+    /// This is the bracket reset of a grand final, as the upper bracket champion has a second life.
+    ResetOf(usize),
+}
+
+/// The part of the bracket a match belongs to
+#[derive(Serialize, Clone, Copy, PartialEq, Debug)]
+pub enum BracketSection {
+    /// The winners of a double elimination or simply the whole single elimination bracket
+    Upper,
+    /// The losers of a double elimination with their second life
+    Lower,
+    /// The final between the champions of both brackets including a possible reset
+    GrandFinal,
+}
+
+/// A match of the bracket before any result is known
+pub struct BracketMatch {
+    pub section: BracketSection,
+    /// The round of the match inside its section, starting with 1
+    pub round: u8,
+    pub first: Slot,
+    pub second: Slot,
+}
+
+/// Who is actually sitting in a slot after applying the results
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum Occupant {
+    /// The player with the given index in the league
+    Player(u8),
+    /// Nobody, the other side advances without playing
+    Bye,
+    /// Still depends on an unfinished match
+    Pending,
+}
+
+impl Occupant {
+    /// Get the player index if a player sits in the slot
+    pub fn player(&self) -> Option<u8> {
+        match self {
+            Occupant::Player(index) => Some(*index),
+            _ => None,
+        }
+    }
+}
+
+/// A match of the bracket with the results applied
+#[derive(Clone, Copy)]
+pub struct ResolvedMatch {
+    pub section: BracketSection,
+    pub round: u8,
+    pub first: Occupant,
+    pub second: Occupant,
+    /// `Pending` as long as the match is not decided, `Bye` if nobody advances
+    pub winner: Occupant,
+    /// How often both players already met earlier in the bracket
+    pub series: u8,
+}
+
+impl ResolvedMatch {
+    /// The side which did not win, `Pending` as long as the match is not decided
+    ///
+    /// If nobody won, e.g. as both forfeited, nobody is the loser either.
+    pub fn loser(&self) -> Occupant {
+        match self.winner {
+            Occupant::Pending => Occupant::Pending,
+            Occupant::Bye => Occupant::Bye,
+            winner if winner == self.first => self.second,
+            _ => self.first,
+        }
+    }
+
+    /// Check if both players are known but the match is not decided yet
+    pub fn is_open(&self) -> bool {
+        self.first.player().is_some()
+            && self.second.player().is_some()
+            && self.winner == Occupant::Pending
+    }
+
+    /// Check if the match is played between the two given players in any order
+    pub fn is_between(&self, a: u8, b: u8) -> bool {
+        (self.first, self.second) == (Occupant::Player(a), Occupant::Player(b))
+            || (self.first, self.second) == (Occupant::Player(b), Occupant::Player(a))
+    }
+}
+
+/// The generated bracket of a knockout league
+pub struct Bracket {
+    matches: Vec<BracketMatch>,
+}
+
+impl Bracket {
+    /// Generate a single elimination bracket for the given amount of players
+    ///
+    /// The bracket is filled up with byes to the next power of two.
+    /// The seeds are placed in the standard order (1 vs 8, 4 vs 5, 2 vs 7, 3 vs 6 and so on),
+    /// so the top seeds get the byes and only meet each other in the late rounds.
+    pub fn single_elimination(players: u8) -> Self {
+        let (matches, _rounds) = upper_bracket(players);
+        Bracket { matches }
+    }
+
+    /// Generate a double elimination bracket for the given amount of players
+    ///
+    /// The upper bracket is the single elimination bracket.
+    /// Its losers drop down into the lower bracket, which alternates between rounds
+    /// where the dropped players face the lower bracket survivors and rounds among the survivors.
+    /// Every other drop down is placed in reverse order to avoid early rematches.
+    /// The champions of both brackets meet in the grand final.
+    /// With `bracket_reset` a second grand final is played if the upper champion lost the first.
+    pub fn double_elimination(players: u8, bracket_reset: bool) -> Self {
+        let (mut matches, rounds) = upper_bracket(players);
+
+        let mut lower_round = 0;
+        let mut push_lower_round = |matches: &mut Vec<BracketMatch>, pairs: Vec<(Slot, Slot)>| {
+            lower_round += 1;
+            let start = matches.len();
+            for (first, second) in pairs {
+                matches.push(BracketMatch {
+                    section: BracketSection::Lower,
+                    round: lower_round,
+                    first,
+                    second,
+                });
+            }
+            (start..matches.len()).collect::<Vec<usize>>()
+        };
+
+        // The losers of the first round play among themselves
+        let pairs = rounds[0]
+            .chunks(2)
+            .map(|pair| (Slot::LoserOf(pair[0]), Slot::LoserOf(pair[1])))
+            .collect();
+        let mut survivors = push_lower_round(&mut matches, pairs);
+
+        for (round, upper) in rounds.iter().enumerate().skip(1) {
+            // The losers of the upper round drop down
+            let mut dropped = upper.clone();
+            if round % 2 == 1 {
+                dropped.reverse();
+            }
+            let pairs = survivors
+                .iter()
+                .zip(dropped)
+                .map(|(survivor, loser)| (Slot::WinnerOf(*survivor), Slot::LoserOf(loser)))
+                .collect();
+            survivors = push_lower_round(&mut matches, pairs);
+
+            // The survivors play among themselves until they are as many as the next drop
+            if survivors.len() > 1 {
+                let pairs = survivors
+                    .chunks(2)
+                    .map(|pair| (Slot::WinnerOf(pair[0]), Slot::WinnerOf(pair[1])))
+                    .collect();
+                survivors = push_lower_round(&mut matches, pairs);
+            }
+        }
+
+        let upper_final = *rounds.last().unwrap().first().unwrap();
+        let lower_final = survivors[0];
+        matches.push(BracketMatch {
+            section: BracketSection::GrandFinal,
+            round: 1,
+            first: Slot::WinnerOf(upper_final),
+            second: Slot::WinnerOf(lower_final),
+        });
+        if bracket_reset {
+            let grand_final = matches.len() - 1;
+            matches.push(BracketMatch {
+                section: BracketSection::GrandFinal,
+                round: 2,
+                first: Slot::WinnerOf(grand_final),
+                second: Slot::ResetOf(grand_final),
+            });
+        }
+
+        Bracket { matches }
+    }
+
+    /// Fill the bracket with the results
+    ///
+    /// `seeds` maps the seeds to the player indices of the league.
+    /// `winner` returns who advances from the match between two players,
+    /// i.e. one of them, `Bye` if both are out or `Pending` if it is not decided yet.
+    /// As two players may meet more than once, it also gets the number of their earlier meetings.
+    /// A player facing a bye advances without a match.
+    pub fn resolve<F>(&self, seeds: &[u8], mut winner: F) -> Vec<ResolvedMatch>
+    where
+        F: FnMut(u8, u8, u8) -> Occupant,
+    {
+        let mut resolved: Vec<ResolvedMatch> = Vec::with_capacity(self.matches.len());
+        let mut meetings: HashMap<(u8, u8), u8> = HashMap::new();
+        for bracket_match in self.matches.iter() {
+            let occupant = |slot: Slot, resolved: &Vec<ResolvedMatch>| match slot {
+                Slot::Seed(seed) => Occupant::Player(seeds[seed as usize]),
+                Slot::Bye => Occupant::Bye,
+                Slot::WinnerOf(index) => resolved[index].winner,
+                Slot::LoserOf(index) => resolved[index].loser(),
+                Slot::ResetOf(index) => match resolved[index].winner {
+                    Occupant::Pending => Occupant::Pending,
+                    Occupant::Bye => Occupant::Bye,
+                    winner if winner == resolved[index].first => Occupant::Bye,
+                    _ => resolved[index].first,
+                },
+            };
+            let first = occupant(bracket_match.first, &resolved);
+            let second = occupant(bracket_match.second, &resolved);
+            let mut series = 0;
+            let decided = match (first, second) {
+                (Occupant::Player(a), Occupant::Player(b)) => {
+                    let meeting = meetings.entry((a.min(b), a.max(b))).or_insert(0);
+                    series = *meeting;
+                    *meeting += 1;
+                    winner(a, b, series)
+                }
+                (Occupant::Player(_), Occupant::Bye) => first,
+                (Occupant::Bye, Occupant::Player(_)) => second,
+                (Occupant::Bye, Occupant::Bye) => Occupant::Bye,
+                _ => Occupant::Pending,
+            };
+            resolved.push(ResolvedMatch {
+                section: bracket_match.section,
+                round: bracket_match.round,
+                first,
+                second,
+                winner: decided,
+                series,
+            });
+        }
+        resolved
+    }
+
+    /// The number of matches which are actually played, i.e. without byes
+    ///
+    /// This does not depend on the results, so any outcome can be assumed.
+    /// Only a bracket reset may be played or not, it is counted as well.
+    pub fn expected_matches(&self, players: u8) -> u64 {
+        let seeds = (0..players).collect::<Vec<u8>>();
+        let mut played = 0;
+        // The first side always wins, so a reset is never played in here
+        self.resolve(&seeds, |a, _b, _series| {
+            played += 1;
+            Occupant::Player(a)
+        });
+        let resets = self
+            .matches
+            .iter()
+            .filter(|bracket_match| matches!(bracket_match.second, Slot::ResetOf(_)))
+            .count() as u64;
+        played + resets
+    }
+}
+
+/// Generate the upper bracket, i.e. a single elimination bracket
+///
+/// Additionally the indices of the matches are returned round by round.
+fn upper_bracket(players: u8) -> (Vec<BracketMatch>, Vec<Vec<usize>>) {
+    let size = (players as usize).next_power_of_two();
+    let mut matches = Vec::new();
+
+    let order = seed_order(size);
+    for pair in order.chunks(2) {
+        let slot = |seed: usize| {
+            if seed < players as usize {
+                Slot::Seed(seed as u8)
+            } else {
+                Slot::Bye
+            }
+        };
+        matches.push(BracketMatch {
+            section: BracketSection::Upper,
+            round: 1,
+            first: slot(pair[0]),
+            second: slot(pair[1]),
+        });
+    }
+    let mut rounds = vec![(0..matches.len()).collect::<Vec<usize>>()];
+
+    // Every further round pairs the winners of two neighbouring matches of the round before
+    while rounds.last().unwrap().len() > 1 {
+        let round = rounds.len() as u8 + 1;
+        let start = matches.len();
+        for pair in rounds.last().unwrap().chunks(2) {
+            matches.push(BracketMatch {
+                section: BracketSection::Upper,
+                round,
+                first: Slot::WinnerOf(pair[0]),
+                second: Slot::WinnerOf(pair[1]),
+            });
+        }
+        rounds.push((start..matches.len()).collect());
+    }
+
+    (matches, rounds)
+}
+
+/// Check if every match of a resolved bracket is decided
+pub fn is_decided(resolved: &[ResolvedMatch]) -> bool {
+    resolved
+        .iter()
+        .all(|resolved_match| resolved_match.winner != Occupant::Pending)
+}
+
+/// The standard seed order of a bracket with `size` slots (a power of two)
+///
+/// Each round of doubling puts the opponent `size + 1 - seed` next to every seed,
+/// which yields 1, 8, 4, 5, 2, 7, 3, 6 for eight slots (zero based here).
+fn seed_order(size: usize) -> Vec<usize> {
+    let mut order = vec![0];
+    while order.len() < size {
+        let doubled = order.len() * 2;
+        order = order
+            .iter()
+            .flat_map(|seed| [*seed, doubled - 1 - seed])
+            .collect();
+    }
+    order
+}
+
+/// Helper enum for serialization of a slot of the bracket in the views
+#[derive(Serialize, PartialEq, Debug)]
+pub enum HelperOccupant {
+    Player(String),
+    Bye,
+    Pending,
+}
+
+/// Helper struct for serialization of a bracket match in the views
+#[derive(Serialize)]
+pub struct HelperBracketMatch {
+    pub section: BracketSection,
+    pub round: u8,
+    pub first: HelperOccupant,
+    pub second: HelperOccupant,
+    pub winner: HelperOccupant,
+}
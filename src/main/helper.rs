@@ -13,6 +13,9 @@ pub struct CollectionKeyTuple {
     players_key: Vec<u8>,
     trusted_key: Vec<u8>,
     matches_key: Vec<u8>,
+    rounds_key: Vec<u8>,
+    corrections_key: Vec<u8>,
+    disputes_key: Vec<u8>,
 }
 
 impl CollectionKeyTuple {
@@ -31,11 +34,26 @@ impl CollectionKeyTuple {
         self.matches_key.clone()
     }
 
+    /// Get the key for the rounds collection
+    pub fn get_rounds_key(&self) -> Vec<u8> {
+        self.rounds_key.clone()
+    }
+
+    /// Get the key for the corrections collection
+    pub fn get_corrections_key(&self) -> Vec<u8> {
+        self.corrections_key.clone()
+    }
+
+    /// Get the key for the disputes collection
+    pub fn get_disputes_key(&self) -> Vec<u8> {
+        self.disputes_key.clone()
+    }
+
     /// Create a new key collection tuple from a _unique_ string
     pub fn new(seed: &String) -> Self {
         let mut r = env::sha256(seed.as_bytes());
 
-        // Simply add another byte to the usual sha256 to make three unique but similar keys
+        // Simply add another byte to the usual sha256 to make unique but similar keys
         r.push(0);
         let last_index = r.len() - 1;
 
@@ -47,12 +65,24 @@ impl CollectionKeyTuple {
         let trusted_key = r.clone();
 
         r[last_index] = 2;
-        let matches_key = r; // last one can be moved instead of a clone
+        let matches_key = r.clone();
+
+        r[last_index] = 3;
+        let rounds_key = r.clone();
+
+        r[last_index] = 4;
+        let corrections_key = r.clone();
+
+        r[last_index] = 5;
+        let disputes_key = r; // last one can be moved instead of a clone
 
         CollectionKeyTuple {
             players_key,
             trusted_key,
             matches_key,
+            rounds_key,
+            corrections_key,
+            disputes_key,
         }
     }
 }
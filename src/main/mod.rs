@@ -5,26 +5,46 @@
 //! I decided for `GameMatch`
 //!
 
+pub mod bracket;
 pub mod helper;
+pub mod schedule;
+pub mod standings;
+pub mod swiss;
 
+use bracket::{
+    Bracket, BracketSection, HelperBracketMatch, HelperOccupant, Occupant, ResolvedMatch,
+};
 use helper::CollectionKeyTuple;
+use schedule::{HelperMatchday, Matchday};
+use standings::{FinishedMatch, PointsScheme, StandingsEntry, Tiebreaker};
+use swiss::{HelperSwissRound, SwissRound};
 
 use near_sdk::borsh::{self, BorshDeserialize, BorshSerialize};
-use near_sdk::collections::LookupSet;
 use near_sdk::collections::UnorderedMap;
+use near_sdk::collections::UnorderedSet;
 use near_sdk::collections::Vector;
 use near_sdk::env;
+use near_sdk::json_types::{U128, U64};
 use near_sdk::require;
-use near_sdk::AccountId;
+use near_sdk::{AccountId, Balance, StorageUsage};
 
 // Connection to the games in the other module
 use crate::game_module::Game;
+use crate::game_module::GameOutcome;
 use crate::game_module::GameType;
 
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// Estimated storage of a `GameMatch` without games, including its key and the map overhead
+pub const STORAGE_PER_MATCH: StorageUsage = 200;
+/// Estimated storage of a `Game` with small game data
+pub const STORAGE_PER_GAME: StorageUsage = 100;
+/// The most players a league can have, so the player indices and the schedules fit into a `u8`
+pub const MAX_PLAYERS: u8 = 128;
 
 /// The contestants of a `GameMatch`.
-#[derive(BorshDeserialize, BorshSerialize)]
+#[derive(BorshDeserialize, BorshSerialize, PartialEq)]
 pub struct PlayerPair {
     first: u8,
     second: u8,
@@ -67,6 +87,24 @@ impl PlayerPair {
     }
 }
 
+/// The key of a `GameMatch` in the league
+///
+/// Usually two players only meet once.
+/// In some formats they may meet again, e.g. in the lower bracket of a double elimination.
+/// The `series` counts these meetings starting with 0.
+#[derive(BorshDeserialize, BorshSerialize, PartialEq)]
+pub struct MatchKey {
+    pair: PlayerPair,
+    series: u8,
+}
+
+impl MatchKey {
+    /// Create the key for the `series`-th meeting of the players in the `pair`
+    pub fn new(pair: PlayerPair, series: u8) -> Self {
+        MatchKey { pair, series }
+    }
+}
+
 /// The actual league object holding everything together.
 ///
 /// The properties are hold in an additional struct.
@@ -76,14 +114,39 @@ impl PlayerPair {
 pub struct League {
     /// The properties of the league.
     properties: UpgradeableLeagueProperties,
-    /// The (constant) list of all participants of the league
+    /// The list of all participants of the league.
+    /// It never changes once the league started, withdrawn players stay in it to keep the indices.
     players: Vector<String>,
     /// The actual games between all contestants.
-    game_matches: UnorderedMap<PlayerPair, GameMatch>,
+    game_matches: UnorderedMap<MatchKey, GameMatch>,
+    /// The paired rounds of a swiss league, the last one is the active round. Empty in other formats.
+    rounds: Vector<SwissRound>,
     /// The set of accounts being allowed to manipulate the league. Can be seen as moderators.
-    trusted_account_ids: LookupSet<AccountId>,
-    /// The owner of the league (in this context the same as the creator)
+    trusted_account_ids: UnorderedSet<AccountId>,
+    /// The owner of the league (initially the creator)
     owner: AccountId,
+    /// The account the ownership is proposed to until it accepts
+    proposed_owner: Option<AccountId>,
+    /// The deposit paying for the storage of the league. The rest is refunded on deletion.
+    pledge: Balance,
+    /// The storage in bytes used by the league
+    storage_usage: StorageUsage,
+    /// Where the league is in its life, which decides the calls it allows
+    lifecycle: Lifecycle,
+    /// The current phase of the league
+    phase: Phase,
+    /// The player indices in seed order once the playoffs started
+    playoff_seeds: Vec<u8>,
+    /// The indices of the players who withdrew from the league
+    withdrawn: Vec<u8>,
+    /// The accounts linked to the players, who may report the games of their own matches
+    player_accounts: Vec<(u8, AccountId)>,
+    /// The accounts players asked to link to themselves, waiting for the owner or a trusted account
+    link_requests: Vec<(u8, AccountId)>,
+    /// The audit trail of every correction of a recorded game
+    corrections: Vector<Correction>,
+    /// Every dispute of a result, the open ones freeze their match
+    disputes: Vector<Dispute>,
 }
 
 impl League {
@@ -92,21 +155,251 @@ impl League {
     /// The `keys` have to be given as the league has no idea how it is named.
     /// The collections `players` and `trusted_Account_ids` are already created
     /// and as such the keys are created, too.
+    /// The `pledge` is the deposit attached on creation.
+    /// The league is a draft until it opens the registration or is started.
     pub fn new(
         keys: CollectionKeyTuple,
         properties: UpgradeableLeagueProperties,
         players: Vector<String>,
-        trusted_account_ids: LookupSet<AccountId>,
+        trusted_account_ids: UnorderedSet<AccountId>,
+        pledge: Balance,
     ) -> Self {
+        let rounds = Vector::new(keys.get_rounds_key());
+        let corrections = Vector::new(keys.get_corrections_key());
+        let disputes = Vector::new(keys.get_disputes_key());
         League {
             properties,
             players,
             trusted_account_ids,
             game_matches: UnorderedMap::new(keys.get_matches_key()),
+            rounds,
             owner: env::predecessor_account_id(),
+            proposed_owner: None,
+            pledge,
+            storage_usage: 0,
+            lifecycle: Lifecycle::Draft,
+            phase: Phase::Regular,
+            playoff_seeds: Vec::new(),
+            withdrawn: Vec::new(),
+            player_accounts: Vec::new(),
+            link_requests: Vec::new(),
+            corrections,
+            disputes,
+        }
+    }
+
+    /// Start the league with the players registered so far
+    ///
+    /// The roster is frozen from now on, so the players are checked against the properties.
+    /// The players are assigned to the `groups` if the format has groups, see `LeagueOptions`.
+    /// A swiss league gets its first round paired right away.
+    ///
+    /// Only the owner may start the league.
+    pub fn start(&mut self, groups: Option<Vec<u8>>) {
+        require!(
+            self.caller_is_owner(),
+            "Only the owner may start the league"
+        );
+        self.transition(Lifecycle::Running);
+        let players = self.players.to_vec();
+        require!(players.len() > 2, "League needs at least 3 participant");
+        let format = self.properties.get_format();
+        format.assert_valid(players.len(), self.properties.get_draw_rule());
+        self.properties
+            .set_groups(format.assign_groups(players.len(), groups));
+        for rule in self.properties.get_best_of_overrides().iter() {
+            rule.assert_valid(&players);
+        }
+        let matchdays = format
+            .schedule(players.len() as u8)
+            .map_or(0, |schedule| schedule.len());
+        require!(
+            self.properties.get_matchday_deadlines().len() <= matchdays,
+            "Matchday deadlines need a round robin with at least as many matchdays"
+        );
+        if let LeagueFormat::Swiss { .. } = format {
+            self.rounds.push(&swiss::first_round(players.len() as u8));
+        }
+    }
+
+    /// Open the registration of a draft league, so accounts may join
+    ///
+    /// Only the owner may open the registration.
+    pub fn open_registration(&mut self) {
+        require!(
+            self.caller_is_owner(),
+            "Only the owner may change the state of the league"
+        );
+        require!(
+            self.properties.get_registration().is_some(),
+            "League has no registration"
+        );
+        self.transition(Lifecycle::Registration);
+    }
+
+    /// Pause a running league, e.g. during a dispute
+    ///
+    /// No matches can be played until the league is resumed,
+    /// but the trusted accounts may still correct games and resolve disputes.
+    /// Only the owner may pause the league.
+    pub fn pause(&mut self) {
+        require!(
+            self.caller_is_owner(),
+            "Only the owner may change the state of the league"
+        );
+        self.transition(Lifecycle::Paused);
+    }
+
+    /// Resume a paused league
+    ///
+    /// Only the owner may resume the league.
+    pub fn resume(&mut self) {
+        require!(
+            self.caller_is_owner(),
+            "Only the owner may change the state of the league"
+        );
+        self.transition(Lifecycle::Running);
+    }
+
+    /// Close the league formally, so no results change anymore
+    ///
+    /// The league has to be finished, except it is explicitely wished by setting `force` to true.
+    /// Every dispute has to be resolved before.
+    /// Only the owner may close the league.
+    pub fn close(&mut self, force: bool) {
+        require!(
+            self.caller_is_owner(),
+            "Only the owner may change the state of the league"
+        );
+        require!(self.is_finished() || force, "League is not finished yet");
+        require!(
+            self.disputes
+                .iter()
+                .all(|dispute| dispute.resolution.is_some()),
+            "Open disputes have to be resolved first"
+        );
+        self.transition(Lifecycle::Finished);
+    }
+
+    /// Archive a closed league, so nothing changes anymore until it is deleted
+    ///
+    /// Only the owner may archive the league.
+    pub fn archive(&mut self) {
+        require!(
+            self.caller_is_owner(),
+            "Only the owner may change the state of the league"
+        );
+        self.transition(Lifecycle::Archived);
+    }
+
+    /// Move the league into the `next` state if the lifecycle allows it
+    fn transition(&mut self, next: Lifecycle) {
+        require!(
+            self.lifecycle.may_become(next),
+            format!("League cannot go from {:?} to {:?}", self.lifecycle, next)
+        );
+        self.lifecycle = next;
+    }
+
+    /// The caller joins the league in registration with the display `name`
+    ///
+    /// The account is linked to the new player, so it may report the games of its matches.
+    /// The registration may be limited by a cap of players and a deadline.
+    pub fn join(&mut self, name: String) {
+        require!(
+            self.lifecycle == Lifecycle::Registration,
+            "Registration is closed"
+        );
+        let registration = self.properties.get_registration();
+        require!(registration.is_some(), "League has no registration");
+        let registration = registration.unwrap();
+        let deadline_passed = registration
+            .deadline
+            .is_some_and(|deadline| env::block_timestamp() > deadline.0);
+        require!(!deadline_passed, "Registration deadline has passed");
+        require!(
+            self.players.len() < registration.cap.unwrap_or(MAX_PLAYERS).min(MAX_PLAYERS) as u64,
+            "League is full"
+        );
+        require!(
+            !self.players.iter().any(|player| player == name),
+            "Name is already taken"
+        );
+        let caller = env::predecessor_account_id();
+        require!(
+            self.player_of(&caller).is_none(),
+            "Account is already registered"
+        );
+        self.link_requests
+            .retain(|(_, requesting)| *requesting != caller);
+        self.player_accounts
+            .push((self.players.len() as u8, caller));
+        self.players.push(&name);
+    }
+
+    /// Remove an entry of the league in registration
+    ///
+    /// The players after it move up, so the order of registration is kept.
+    ///
+    /// Only the owner may remove entries.
+    pub fn remove_entry(&mut self, name: &String) {
+        require!(self.caller_is_owner(), "Only the owner may remove entries");
+        require!(!self.has_started(), "Registration is closed");
+        let mut players = self.players.to_vec();
+        let index = players.iter().position(|player| player == name);
+        require!(index.is_some(), "Player not found in the league");
+        let index = index.unwrap();
+        players.remove(index);
+        self.players.clear();
+        self.players.extend(players);
+
+        // The linked accounts and the requests refer to the indices of the players
+        let index = index as u8;
+        self.player_accounts.retain(|(player, _)| *player != index);
+        self.link_requests.retain(|(player, _)| *player != index);
+        for (player, _) in self
+            .player_accounts
+            .iter_mut()
+            .chain(self.link_requests.iter_mut())
+        {
+            if *player > index {
+                *player -= 1;
+            }
         }
     }
 
+    /// Get where the league is in its life
+    pub fn lifecycle(&self) -> Lifecycle {
+        self.lifecycle
+    }
+
+    /// Check if the league has started, i.e. its roster is frozen
+    pub fn has_started(&self) -> bool {
+        !matches!(self.lifecycle, Lifecycle::Draft | Lifecycle::Registration)
+    }
+
+    /// Check if the league is closed, i.e. its results do not change anymore
+    pub fn is_closed(&self) -> bool {
+        matches!(self.lifecycle, Lifecycle::Finished | Lifecycle::Archived)
+    }
+
+    /// Make sure the matches of the league can be played right now
+    fn assert_running(&self) {
+        require!(self.has_started(), "League has not started yet");
+        require!(self.lifecycle != Lifecycle::Paused, "League is paused");
+        require!(!self.is_closed(), "League is closed");
+    }
+
+    /// Make sure the results of the league may still change
+    fn assert_not_closed(&self) {
+        require!(!self.is_closed(), "League is closed");
+    }
+
+    /// Make sure the league is not archived, as nothing changes anymore then
+    fn assert_not_archived(&self) {
+        require!(self.lifecycle != Lifecycle::Archived, "League is archived");
+    }
+
     /// Check if the caller of a call on this league is permitted
     ///
     /// Permitted is anybody inside the `trusted_account_ids` set or the `owner`
@@ -121,218 +414,2598 @@ impl League {
         env::predecessor_account_id() == self.owner
     }
 
-    /// Check if the league is finished
+    /// Propose a new owner of the league
     ///
-    /// This means that every match is finished and no additional game can be added.
-    pub fn is_finished(&self) -> bool {
-        let p = self.players.len();
-        // Gaussian sum formula.
-        // It yields to the number of matches where each player played with everybody.
-        // Or at least started...
-        if self.game_matches.len() != p * (p - 1) / 2 {
-            return false;
-        } else {
-            // So in case everyone started to play against each other
-            // it still needs to be confirmed that they also finished
-            for (_pair, game_match) in self.game_matches.iter() {
-                if !game_match.winner(self.properties.get_best_of()).exist() {
-                    return false;
-                }
-            }
-        }
-        true
+    /// The ownership only changes after the proposed account accepted.
+    /// A former proposal is replaced.
+    pub fn propose_owner(&mut self, account_id: &AccountId) {
+        self.assert_not_archived();
+        require!(*account_id != self.owner, "Account is already the owner");
+        self.proposed_owner = Some(account_id.clone());
     }
 
-    /// Add a game to the league
+    /// Cancel a proposed ownership transfer
+    pub fn cancel_ownership_transfer(&mut self) {
+        self.assert_not_archived();
+        require!(
+            self.proposed_owner.is_some(),
+            "No ownership transfer proposed"
+        );
+        self.proposed_owner = None;
+    }
+
+    /// The caller accepts the proposed ownership and becomes the new owner
     ///
-    /// As such add a game with additional `game_data` for the players given by name.
-    /// The indices of these players are searched and then checked if the game can be added to a match.
-    /// The game itself needs to be created by additional conversion of the `game_data` json.
+    /// As the owner is always trusted, the new owner is removed from the trusted accounts.
+    pub fn accept_ownership(&mut self) {
+        self.assert_not_archived();
+        let caller = env::predecessor_account_id();
+        require!(
+            self.proposed_owner.as_ref() == Some(&caller),
+            "The ownership is not proposed to you"
+        );
+        self.trusted_account_ids.remove(&caller);
+        self.owner = caller;
+        self.proposed_owner = None;
+    }
+
+    /// Add an account to the trusted accounts
     ///
-    /// Beware! This method can panic too!
-    pub fn add_game(
-        &mut self,
-        player_names: &(String, String),
-        first_in_tuple_won: bool,
-        game_data: &String,
-    ) {
-        // Wonderful iteration through all the names to find the correct indices
-        // Maybe it could be done more beautiful but I think this is well enough
-        let mut first: Option<u8> = None;
-        let mut second: Option<u8> = None;
-        for (idx, i) in self.players.iter().enumerate() {
-            if i == player_names.0 {
-                first = Some(idx as u8);
-            } else if i == player_names.1 {
-                second = Some(idx as u8);
+    /// The owner is trusted anyway and does not need to be added.
+    pub fn add_trusted_account(&mut self, account_id: &AccountId) {
+        self.assert_not_archived();
+        require!(*account_id != self.owner, "The owner is always trusted");
+        require!(
+            self.trusted_account_ids.insert(account_id),
+            "Account is already trusted"
+        );
+    }
+
+    /// Remove an account from the trusted accounts
+    pub fn remove_trusted_account(&mut self, account_id: &AccountId) {
+        self.assert_not_archived();
+        require!(
+            self.trusted_account_ids.remove(account_id),
+            "Account is not trusted"
+        );
+    }
+
+    /// List all trusted accounts (without the owner)
+    pub fn trusted_accounts(&self) -> Vec<AccountId> {
+        self.trusted_account_ids.to_vec()
+    }
+
+    /// Get the owner of the league
+    pub fn owner(&self) -> AccountId {
+        self.owner.clone()
+    }
+
+    /// Remove all entries of the nested collections from the storage
+    ///
+    /// The collections live under keys derived from the league's name.
+    /// Without clearing them a recreated league with the same name would find the old entries.
+    pub fn clear(&mut self) {
+        self.players.clear();
+        self.game_matches.clear();
+        self.rounds.clear();
+        self.trusted_account_ids.clear();
+        self.corrections.clear();
+        self.disputes.clear();
+    }
+
+    /// The number of matches to be played in the league
+    pub fn expected_matches(&self) -> u64 {
+        let p = self.players.len();
+        match self.properties.get_format() {
+            // Gaussian sum formula.
+            // It yields to the number of matches where each player played with everybody.
+            LeagueFormat::RoundRobin => p * (p - 1) / 2,
+            // Twice the matches of a round robin
+            LeagueFormat::DoubleRoundRobin => p * (p - 1),
+            // Everybody but the one with the bye plays in every round
+            LeagueFormat::Swiss { rounds } => rounds as u64 * (p / 2),
+            LeagueFormat::SingleElimination | LeagueFormat::DoubleElimination { .. } => self
+                .bracket()
+                .unwrap()
+                .expected_matches(self.players.len() as u8),
+            // A round robin per group and a single elimination of all advancing players
+            LeagueFormat::GroupsAndPlayoffs { groups, advance } => {
+                self.group_stage_matches(groups) + groups as u64 * advance as u64 - 1
             }
         }
-        require!(
-            first.is_some() && second.is_some(),
-            "At least one player not found in the league"
-        ); // Check if both indices exist
+    }
 
-        let pair = PlayerPair::new(first.unwrap(), second.unwrap());
-        let game_match = self.game_matches.get(&pair);
+    /// The number of matches in the round robins of all `groups`
+    fn group_stage_matches(&self, groups: u8) -> u64 {
+        (0..groups)
+            .map(|group| {
+                let g = self.group_members(group).len() as u64;
+                g * (g - 1) / 2
+            })
+            .sum()
+    }
 
-        let mut game_match = match game_match {
-            None => GameMatch::new(),
-            Some(m) => m,
-        };
-        require!(
-            !game_match.winner(self.properties.get_best_of()).exist(),
-            "Match is already finished"
-        ); // Check if the game match is already full (has a winner)
+    /// The player indices of a group
+    fn group_members(&self, group: u8) -> Vec<u8> {
+        self.properties
+            .get_groups()
+            .iter()
+            .enumerate()
+            .filter(|(_, g)| **g == group)
+            .map(|(index, _)| index as u8)
+            .collect()
+    }
 
-        // Swaps the win flag if the names were swapped in the first place
-        let first_has_won = pair.is_swapped(first.unwrap()) ^ first_in_tuple_won;
-        let game = Game::new_with_data(first_has_won, self.properties.get_game_type(), game_data);
-        require!(
-            game.is_some(),
-            "Game data cannot be parsed in the game type"
-        ); // Check if game is creatable (thus the game data is convertible = the game data conforms the corresponding data struct)
-        game_match.add_game(game.unwrap());
-        self.game_matches.insert(&pair, &game_match);
+    /// Generate the bracket if the league is played in a knockout format
+    fn bracket(&self) -> Option<Bracket> {
+        match self.properties.get_format() {
+            LeagueFormat::RoundRobin
+            | LeagueFormat::DoubleRoundRobin
+            | LeagueFormat::Swiss { .. } => None,
+            LeagueFormat::SingleElimination => {
+                Some(Bracket::single_elimination(self.players.len() as u8))
+            }
+            LeagueFormat::DoubleElimination { bracket_reset } => Some(Bracket::double_elimination(
+                self.players.len() as u8,
+                bracket_reset,
+            )),
+            LeagueFormat::GroupsAndPlayoffs { .. } => match self.phase {
+                Phase::Regular => None,
+                Phase::Playoffs => {
+                    Some(Bracket::single_elimination(self.playoff_seeds.len() as u8))
+                }
+            },
+        }
+    }
+
+    /// Generate the matchdays if the league is played in a round robin format
+    fn schedule(&self) -> Option<Vec<Matchday>> {
+        self.properties
+            .get_format()
+            .schedule(self.players.len() as u8)
     }
 
-    /// Summarize the league as a json string
+    /// The key of the match between the `home` and the `away` player of a `matchday`
     ///
-    /// Well, maybe I should just call it serialize?
-    /// But this gets also annoying as every ser/de lib calls some generated functions like that
-    pub fn summarize_league(&self) -> String {
-        let best_of = self.properties.get_best_of();
-        let matches = self
-            .game_matches
+    /// In a double round robin the matchday tells the leg, as both legs have the same players.
+    fn matchday_key(matchday: &Matchday, (home, away): (u8, u8)) -> MatchKey {
+        MatchKey::new(PlayerPair::new(home, away), matchday.series)
+    }
+
+    /// The index of the matchday where the match with the `key` is played
+    fn matchday_of(&self, schedule: &[Matchday], key: &MatchKey) -> usize {
+        schedule
             .iter()
-            .map(|(pair, game_match)| HelperMatch {
-                first_player: self.players.get(pair.first as u64).unwrap(),
-                second_player: self.players.get(pair.second as u64).unwrap(),
-                winner: game_match.winner(best_of),
-                games: game_match.summarize_match(self.properties.get_game_type()),
+            .position(|matchday| {
+                matchday
+                    .pairings
+                    .iter()
+                    .any(|pairing| Self::matchday_key(matchday, *pairing) == *key)
             })
-            .collect::<Vec<HelperMatch>>();
-        //shouldn't be able to fail
-        serde_json::to_string(&HelperLeague { best_of, matches }).unwrap()
+            .unwrap()
     }
-}
 
-/// Helper struct for serialization
-#[derive(Serialize)]
-struct HelperLeague {
-    pub best_of: u8,
-    pub matches: Vec<HelperMatch>,
-}
+    /// The index of the first matchday which still has an unfinished match
+    ///
+    /// The matches of withdrawn players are not waited for.
+    /// Returns `None` if every matchday is finished.
+    fn current_matchday(&self, schedule: &[Matchday]) -> Option<usize> {
+        schedule.iter().position(|matchday| {
+            !matchday.pairings.iter().all(|pairing| {
+                let key = Self::matchday_key(matchday, *pairing);
+                self.involves_withdrawn(&key)
+                    || self
+                        .game_matches
+                        .get(&key)
+                        .is_some_and(|game_match| self.match_winner(&game_match).exist())
+            })
+        })
+    }
 
-/// Helper struct for serialization
-#[derive(Serialize)]
-struct HelperMatch {
-    pub first_player: String,
-    pub second_player: String,
-    pub winner: Winner,
-    pub games: Vec<HelperGame>,
-}
+    /// Every match which could be played right now and is not finished yet
+    ///
+    /// Each one comes with its stage and its round, which is the matchday in a round robin.
+    /// In a swiss league these are the matches of the active round,
+    /// in a knockout format the open matches of the bracket.
+    /// The matches of withdrawn players are left out, as nobody plays them anymore.
+    fn playable_matches(&self) -> Vec<(MatchKey, Stage, Option<u8>)> {
+        let mut playable = self.open_matches();
+        playable.retain(|(key, _, _)| !self.involves_withdrawn(key));
+        playable
+    }
 
-/// Helper struct for serialization
-///
-/// unfortunately the data is also as a string although it is a nested json
-/// TODO solve the nested JSON better
-#[derive(Serialize)]
-struct HelperGame {
-    pub first_player_is_winner: bool,
-    pub data: String,
-}
+    /// Every match which is not finished yet but would be playable if nobody withdrew
+    fn open_matches(&self) -> Vec<(MatchKey, Stage, Option<u8>)> {
+        let mut playable = Vec::new();
+        if let Some(bracket) = self.bracket() {
+            let offset = self.bracket_series_offset();
+            for resolved_match in self.resolve_bracket(&bracket) {
+                if let (true, Some(a), Some(b)) = (
+                    resolved_match.is_open(),
+                    resolved_match.first.player(),
+                    resolved_match.second.player(),
+                ) {
+                    playable.push((
+                        MatchKey::new(PlayerPair::new(a, b), resolved_match.series + offset),
+                        Stage::from(resolved_match.section),
+                        Some(resolved_match.round),
+                    ));
+                }
+            }
+            return playable;
+        }
+        match self.properties.get_format() {
+            LeagueFormat::Swiss { .. } => {
+                let round = self.rounds.len() as u8;
+                for (a, b) in self.rounds.get(round as u64 - 1).unwrap().pairings {
+                    playable.push((
+                        MatchKey::new(PlayerPair::new(a, b), 0),
+                        Stage::Regular,
+                        Some(round),
+                    ));
+                }
+            }
+            LeagueFormat::GroupsAndPlayoffs { groups, .. } => {
+                for group in 0..groups {
+                    let members = self.group_members(group);
+                    for (index, a) in members.iter().enumerate() {
+                        for b in members.iter().skip(index + 1) {
+                            playable.push((
+                                MatchKey::new(PlayerPair::new(*a, *b), 0),
+                                Stage::Regular,
+                                None,
+                            ));
+                        }
+                    }
+                }
+            }
+            _ => {
+                for (index, matchday) in self.schedule().unwrap().iter().enumerate() {
+                    for pairing in &matchday.pairings {
+                        playable.push((
+                            Self::matchday_key(matchday, *pairing),
+                            Stage::Regular,
+                            Some(index as u8 + 1),
+                        ));
+                    }
+                }
+            }
+        }
+        playable.retain(|(key, _, _)| {
+            !self
+                .game_matches
+                .get(key)
+                .is_some_and(|game_match| self.match_winner(&game_match).exist())
+        });
+        playable
+    }
 
-/// The upgradeable enum for the properties to be able to easily upgrade the league
-#[derive(BorshDeserialize, BorshSerialize)]
-pub enum UpgradeableLeagueProperties {
-    V1(LeagueProperties),
-}
+    /// The deadline of a match in the given `round`
+    ///
+    /// In a round robin the deadline of the matchday is used if there is one,
+    /// otherwise the deadline of the whole league.
+    fn deadline(&self, round: Option<u8>) -> Option<u64> {
+        let matchday_deadline = round
+            .filter(|_| self.properties.get_format().has_schedule())
+            .and_then(|round| {
+                self.properties
+                    .get_matchday_deadlines()
+                    .get(round as usize - 1)
+                    .copied()
+            });
+        matchday_deadline.or_else(|| self.properties.get_deadline())
+    }
 
-/// Current version of the properties
-#[derive(BorshDeserialize, BorshSerialize)]
-pub struct LeagueProperties {
-    /// The maximum amount of games each `GameMatch` may have
-    pub best_of: u8,
-    /// The actual type of the game which is played.
-    pub game_type: GameType,
-}
+    /// Forfeit every match whose deadline passed
+    ///
+    /// The `ForfeitPolicy` of the league decides if a match is lost by both or only by one of them.
+    /// Forfeits may make new matches playable, e.g. the next round of the bracket,
+    /// so it goes on until no overdue match is left.
+    /// Returns the number of forfeited matches.
+    pub fn forfeit_overdue_matches(&mut self) -> u32 {
+        require!(
+            self.caller_is_allowed(),
+            "You may not forfeit matches of the league"
+        );
+        self.assert_running();
+        let now = env::block_timestamp();
+        let mut forfeited = 0;
+        loop {
+            let overdue = self
+                .playable_matches()
+                .into_iter()
+                .filter(|(key, _, round)| {
+                    !self.is_frozen(key)
+                        && self.deadline(*round).is_some_and(|deadline| deadline < now)
+                })
+                .collect::<Vec<(MatchKey, Stage, Option<u8>)>>();
+            if overdue.is_empty() {
+                return forfeited;
+            }
+            for (key, stage, round) in overdue {
+                let mut game_match = match self.game_matches.get(&key) {
+                    Some(game_match) => game_match,
+                    None => {
+                        GameMatch::new(self.resolve_best_of(&key.pair, key.series, stage, round))
+                    }
+                };
+                let winner = match self.properties.get_forfeit_policy() {
+                    ForfeitPolicy::DoubleForfeit => Winner::NoWinner,
+                    ForfeitPolicy::Walkover => {
+                        let (a, b) = game_match.score();
+                        match a.cmp(&b) {
+                            std::cmp::Ordering::Greater => Winner::FirstPlayer,
+                            std::cmp::Ordering::Less => Winner::SecondPlayer,
+                            std::cmp::Ordering::Equal => Winner::NoWinner,
+                        }
+                    }
+                };
+                game_match.forfeit(winner, ForfeitReason::Deadline);
+                self.game_matches.insert(&key, &game_match);
+                forfeited += 1;
+            }
+            self.advance();
+        }
+    }
 
-impl UpgradeableLeagueProperties {
-    /// Convenient implementation to get the `best_of` value independant of the `LeagueProperties` version
-    pub fn get_best_of(&self) -> u8 {
-        match self {
-            UpgradeableLeagueProperties::V1(prop) => prop.best_of,
+    /// Fill the bracket with the results of the game matches
+    ///
+    /// The players are seeded in the order they were given on creation
+    /// or by their placement in the groups for the playoffs.
+    fn resolve_bracket(&self, bracket: &Bracket) -> Vec<ResolvedMatch> {
+        let seeds = match self.phase {
+            Phase::Regular => (0..self.players.len() as u8).collect::<Vec<u8>>(),
+            Phase::Playoffs => self.playoff_seeds.clone(),
+        };
+        let offset = self.bracket_series_offset();
+        bracket.resolve(&seeds, |a, b, series| {
+            let key = MatchKey::new(PlayerPair::new(a, b), series + offset);
+            let game_match = match self.game_matches.get(&key) {
+                Some(game_match) => game_match,
+                None => return Occupant::Pending,
+            };
+            match self.match_winner(&game_match) {
+                Winner::FirstPlayer => Occupant::Player(key.pair.first),
+                Winner::SecondPlayer => Occupant::Player(key.pair.second),
+                // Nobody advances if both forfeited
+                Winner::NoWinner => Occupant::Bye,
+                _ => Occupant::Pending,
+            }
+        })
+    }
+
+    /// The series of the first meeting in the bracket
+    ///
+    /// Players of the same group already met once before the playoffs.
+    fn bracket_series_offset(&self) -> u8 {
+        match self.phase {
+            Phase::Regular => 0,
+            Phase::Playoffs => 1,
         }
     }
 
-    /// Convenient implementation to get the `game_type` value independant of the `LeagueProperties` version
-    pub fn get_game_type(&self) -> GameType {
-        match self {
-            UpgradeableLeagueProperties::V1(prop) => prop.game_type.clone(),
+    /// Estimate the storage the matches will need which were not played yet
+    ///
+    /// The estimation assumes that every match takes all games of the highest `best_of`.
+    pub fn reserved_storage(&self) -> StorageUsage {
+        // The matches are not known before the roster is complete
+        if !self.has_started() {
+            return 0;
         }
+        let open_matches = self.expected_matches() - self.game_matches.len();
+        let best_of = self
+            .properties
+            .get_best_of_overrides()
+            .iter()
+            .map(|rule| rule.best_of())
+            .fold(self.properties.get_best_of(), u8::max);
+        open_matches * (STORAGE_PER_MATCH + best_of as u64 * STORAGE_PER_GAME)
     }
-}
 
-/// Description who the winner is if he exists
-#[derive(Serialize)]
-pub enum Winner {
-    FirstPlayer,
-    SecondPlayer,
-    None,
-}
+    /// Add a deposit to the pledge
+    pub fn top_up(&mut self, amount: Balance) {
+        self.pledge += amount;
+    }
 
-impl Winner {
-    /// Check if a winner exists
+    /// Account the storage change since `initial_storage` to the league
+    pub fn account_storage(&mut self, initial_storage: StorageUsage) {
+        let current = env::storage_usage();
+        if current >= initial_storage {
+            self.storage_usage += current - initial_storage;
+        } else {
+            self.storage_usage = self.storage_usage.saturating_sub(initial_storage - current);
+        }
+    }
+
+    /// Check if the pledge covers the used storage and additionally the `reserved` storage
+    pub fn pledge_covers(&self, reserved: StorageUsage) -> bool {
+        (self.storage_usage + reserved) as Balance * env::storage_byte_cost() <= self.pledge
+    }
+
+    /// Calculate the refund of the pledge after `freed` bytes of the league were removed
     ///
-    /// If not it means that the game match is still ongoing.
-    pub fn exist(&self) -> bool {
-        !matches!(self, Winner::None)
+    /// Storage which is still occupied by the league stays paid by the pledge.
+    pub fn refund(&self, freed: StorageUsage) -> Balance {
+        let remaining = self.storage_usage.saturating_sub(freed);
+        self.pledge
+            .saturating_sub(remaining as Balance * env::storage_byte_cost())
     }
-}
 
-/// The match between two contestants.
-///
-/// This contains all games where the max is given by the league properties (`best_of`).
-/// The GameVariants type must be the same as in the league properties (`game_type`)
-/// The pair of the contestants is given by the `PlayerPair` typed key.
-#[derive(BorshDeserialize, BorshSerialize, Default)]
-pub struct GameMatch {
-    /// The vector containing the games
-    games: Vec<Game>,
-}
+    /// Summarize the pledge and the storage for the views
+    pub fn summarize_pledge(&self) -> HelperPledge {
+        HelperPledge {
+            pledge: U128(self.pledge),
+            storage_usage: self.storage_usage,
+            storage_cost: U128(self.storage_usage as Balance * env::storage_byte_cost()),
+        }
+    }
 
-impl GameMatch {
-    /// Create a new empty game match
-    pub fn new() -> Self {
-        GameMatch { games: Vec::new() }
+    /// The winner of a match with its own best_of and the league's draw rule
+    fn match_winner(&self, game_match: &GameMatch) -> Winner {
+        game_match.winner(game_match.best_of(), self.properties.get_draw_rule())
     }
 
-    /// Return the winner of a game match
+    /// Find the pair of two players by their names
+    fn pair_of(&self, players: &(String, String)) -> Option<PlayerPair> {
+        let index = |name: &String| {
+            self.players
+                .iter()
+                .position(|player| player == *name)
+                .map(|index| index as u8)
+        };
+        Some(PlayerPair::new(index(&players.0)?, index(&players.1)?))
+    }
+
+    /// Resolve the best_of of a new match from the overrides of the league
     ///
-    /// This checks each game and returns the winner according to the ''best of'' rules.
-    /// The winner can also be not determined yet due to missing games
-    pub fn winner(&self, best_of: u8) -> Winner {
-        let mut a = 0;
-        let mut b = 0;
-        for i in 0..best_of {
-            match self.games.get(i as usize) {
-                None => break,
-                Some(s) => match s.first_player_won() {
-                    true => a += 1,
-                    false => b += 1,
-                },
+    /// A rule for the specific match wins over a rule for its round, which wins over a rule for its stage.
+    /// Without any rule the league's `best_of` applies.
+    fn resolve_best_of(
+        &self,
+        pair: &PlayerPair,
+        series: u8,
+        stage: Stage,
+        round: Option<u8>,
+    ) -> u8 {
+        // The precedence of the rule and its best_of
+        let mut resolved = (0, self.properties.get_best_of());
+        for rule in self.properties.get_best_of_overrides() {
+            let candidate = match rule {
+                BestOfOverride::Stage { stage: s, best_of } if s == stage => (1, best_of),
+                BestOfOverride::Round {
+                    stage: s,
+                    round: r,
+                    best_of,
+                } if s == stage && Some(r) == round => (2, best_of),
+                BestOfOverride::Match {
+                    players,
+                    series: s,
+                    best_of,
+                } if s == series && self.pair_of(&players).is_some_and(|other| other == *pair) => {
+                    (3, best_of)
+                }
+                _ => continue,
+            };
+            if candidate.0 > resolved.0 {
+                resolved = candidate;
             }
         }
+        resolved.1
+    }
 
-        let win_condition = (best_of + 1) / 2;
-
-        if a == win_condition {
-            return Winner::FirstPlayer;
-        }
-        if b == win_condition {
-            return Winner::SecondPlayer;
-        }
-        Winner::None
+    /// Check if the league is finished
+    ///
+    /// This means that every match is finished and no additional game can be added.
+    pub fn is_finished(&self) -> bool {
+        if !self.has_started() {
+            return false;
+        }
+        // A bracket is finished as soon as every match of it is decided
+        if let Some(bracket) = self.bracket() {
+            return bracket::is_decided(&self.resolve_bracket(&bracket));
+        }
+        // Every match still to be played needs to be finished,
+        // except for the ones of withdrawn players which are not played anymore
+        if !self.playable_matches().is_empty() {
+            return false;
+        }
+        match self.properties.get_format() {
+            // Without another pairing the swiss ends early
+            LeagueFormat::Swiss { rounds } => {
+                self.rounds.len() == rounds as u64 || self.next_swiss_round().is_none()
+            }
+            // The group stage is followed by the playoffs
+            LeagueFormat::GroupsAndPlayoffs { .. } => false,
+            _ => true,
+        }
+    }
+
+    /// Add a game to the league
+    ///
+    /// As such add a game with additional `game_data` for the players given by name.
+    /// The indices of these players are searched and then checked if the game can be added to a match.
+    /// The game itself needs to be created by additional conversion of the `game_data` json.
+    ///
+    /// Only the owner and the trusted accounts may add games.
+    ///
+    /// Beware! This method can panic too!
+    pub fn add_game(
+        &mut self,
+        player_names: &(String, String),
+        outcome: GameOutcome,
+        game_data: &str,
+    ) {
+        require!(
+            self.caller_is_allowed(),
+            "You may not add games to the league"
+        );
+        let (key, mut game_match, swapped) = self.current_match(player_names);
+        let game = self.create_game(outcome, game_data, swapped);
+
+        // A result of a trusted account overrides the one reported by a player
+        game_match.discard_pending();
+        game_match.add_game(game);
+        self.game_matches.insert(&key, &game_match);
+        self.advance();
+    }
+
+    /// Create a game from the `outcome` and the `game_data` in the order of the names
+    ///
+    /// `swapped` tells if the names are in the other order than the players of the match.
+    fn create_game(&self, outcome: GameOutcome, game_data: &str, swapped: bool) -> Game {
+        // Swaps the outcome if the names were swapped in the first place
+        let outcome = if swapped { outcome.swapped() } else { outcome };
+        let game =
+            Game::new_with_data(outcome, self.properties.get_game_type(), game_data, swapped);
+        require!(
+            game.is_some(),
+            "Game data cannot be parsed in the game type"
+        ); // Check if game is creatable (thus the game data is convertible = the game data conforms the corresponding data struct)
+        game.unwrap()
+    }
+
+    /// Link an account to a player
+    ///
+    /// A linked player may report the games of their own matches, which the opponent has to confirm.
+    /// The owner and the trusted accounts link any account, replacing the one linked before.
+    /// A player may only ask to link their own account, which is linked as soon as the owner
+    /// or a trusted account links it as well. Otherwise a player could link a second account
+    /// to their opponent and confirm their own reports.
+    /// A player has one request at a time, so a newer request replaces the older one.
+    ///
+    /// Returns whether the account is linked right away.
+    pub fn link_account(&mut self, name: &String, account_id: AccountId) -> bool {
+        self.assert_not_closed();
+        let player = self.players.iter().position(|player| player == *name);
+        require!(player.is_some(), "Player not found in the league");
+        let player = player.unwrap() as u8;
+        require!(
+            self.player_of(&account_id).unwrap_or(player) == player,
+            "Account is already linked to another player"
+        );
+        if !self.caller_is_allowed() {
+            require!(
+                env::predecessor_account_id() == account_id,
+                "You may only ask to link your own account"
+            );
+            require!(
+                self.account_of(player).is_none(),
+                "Player is already linked to an account"
+            );
+            self.link_requests.retain(|(requested, requesting)| {
+                *requested != player && *requesting != account_id
+            });
+            self.link_requests.push((player, account_id));
+            return false;
+        }
+        self.link_requests
+            .retain(|(requested, requesting)| *requested != player && *requesting != account_id);
+        self.player_accounts.retain(|(linked, _)| *linked != player);
+        self.player_accounts.push((player, account_id));
+        true
+    }
+
+    /// Get the account linked to the `player`
+    fn account_of(&self, player: u8) -> Option<&AccountId> {
+        self.player_accounts
+            .iter()
+            .find(|(linked, _)| *linked == player)
+            .map(|(_, account_id)| account_id)
+    }
+
+    /// Get the player the `account_id` is linked to
+    fn player_of(&self, account_id: &AccountId) -> Option<u8> {
+        self.player_accounts
+            .iter()
+            .find(|(_, linked)| linked == account_id)
+            .map(|(player, _)| *player)
+    }
+
+    /// Report a game of the caller's own match
+    ///
+    /// The caller has to be the account linked to one of the players given by name.
+    /// The game stays pending and does not count until the opponent confirms it.
+    /// Only one game of a match can be pending at a time.
+    pub fn report_game(
+        &mut self,
+        player_names: &(String, String),
+        outcome: GameOutcome,
+        game_data: &str,
+    ) {
+        let reporter = self.player_of(&env::predecessor_account_id());
+        require!(
+            reporter.is_some_and(|reporter| {
+                self.players.get(reporter as u64).unwrap() == player_names.0
+                    || self.players.get(reporter as u64).unwrap() == player_names.1
+            }),
+            "You may only report games of your own matches"
+        );
+        let (key, mut game_match, swapped) = self.current_match(player_names);
+        require!(
+            game_match.get_pending().is_none(),
+            "A game of the match is already pending"
+        );
+        let game = self.create_game(outcome, game_data, swapped);
+        game_match.report(game, reporter.unwrap());
+        self.game_matches.insert(&key, &game_match);
+    }
+
+    /// Confirm the pending game of a match, which counts from now on
+    ///
+    /// Only the account linked to the opponent of the reporter may confirm it.
+    /// The owner and the trusted accounts may confirm it instead.
+    pub fn confirm_game(&mut self, player_names: &(String, String)) {
+        let (key, mut game_match, _swapped) = self.current_match(player_names);
+        let pending = game_match.get_pending();
+        require!(pending.is_some(), "No game of the match is pending");
+        let reporter = pending.unwrap().reporter;
+        let opponent = if reporter == key.pair.first {
+            key.pair.second
+        } else {
+            key.pair.first
+        };
+        require!(
+            self.caller_is_allowed()
+                || self.account_of(opponent) == Some(&env::predecessor_account_id()),
+            "Only the opponent may confirm the pending game"
+        );
+        game_match.confirm_pending();
+        self.game_matches.insert(&key, &game_match);
+        self.advance();
+    }
+
+    /// Reject the pending game of a match
+    ///
+    /// Either player of the match may reject it, e.g. the opponent if the result is wrong.
+    /// The owner and the trusted accounts may reject it as well.
+    pub fn reject_game(&mut self, player_names: &(String, String)) {
+        let (key, mut game_match, _swapped) = self.current_match(player_names);
+        require!(
+            game_match.get_pending().is_some(),
+            "No game of the match is pending"
+        );
+        let caller = self.player_of(&env::predecessor_account_id());
+        require!(
+            self.caller_is_allowed()
+                || caller
+                    .is_some_and(|player| key.pair.first == player || key.pair.second == player),
+            "Only the players of the match may reject the pending game"
+        );
+        game_match.discard_pending();
+        if game_match.game_count() == 0 {
+            self.game_matches.remove(&key);
+        } else {
+            self.game_matches.insert(&key, &game_match);
+        }
+    }
+
+    /// Finish a match by a forfeit instead of its games
+    ///
+    /// The `winner` is one of the players given by name or `None` if both forfeited.
+    /// No game is added, so the statistics of the games stay clean.
+    /// The match has to be playable right now just like for `add_game`.
+    ///
+    /// Only the owner and the trusted accounts may record forfeits.
+    pub fn record_forfeit(
+        &mut self,
+        player_names: &(String, String),
+        winner: Option<String>,
+        reason: String,
+    ) {
+        require!(
+            self.caller_is_allowed(),
+            "You may not record forfeits in the league"
+        );
+        require!(
+            winner
+                .iter()
+                .all(|name| *name == player_names.0 || *name == player_names.1),
+            "The winner has to be one of the players"
+        );
+        let (key, mut game_match, swapped) = self.current_match(player_names);
+        // A forfeited match has no games, so only an unplayed match can be forfeited
+        require!(
+            game_match.games.is_empty(),
+            "Only a match without games can be forfeited"
+        );
+
+        // The winner refers to the order in the key
+        let winner = match winner {
+            None => Winner::NoWinner,
+            Some(name) if (name == player_names.0) != swapped => Winner::FirstPlayer,
+            Some(_) => Winner::SecondPlayer,
+        };
+        game_match.forfeit(winner, ForfeitReason::Recorded(reason));
+        self.game_matches.insert(&key, &game_match);
+        self.advance();
+    }
+
+    /// Correct a recorded game
+    ///
+    /// The game at `index` in the match of the players given by name and the `series` is replaced
+    /// by a game with the `outcome` and the `game_data`, both in the order of the names.
+    /// The original game is kept in the audit trail.
+    ///
+    /// Only the owner and the trusted accounts may correct games.
+    pub fn edit_game(
+        &mut self,
+        player_names: &(String, String),
+        series: u8,
+        index: u8,
+        outcome: GameOutcome,
+        game_data: &str,
+    ) {
+        require!(
+            self.caller_is_allowed(),
+            "You may not correct games of the league"
+        );
+        self.assert_not_closed();
+        let (key, mut game_match, swapped) = self.recorded_match(player_names, series);
+        require!(
+            (index as usize) < game_match.game_count(),
+            "Game not found in the match"
+        );
+        let game = self.create_game(outcome, game_data, swapped);
+        let original = game_match.replace_game(index as usize, game);
+        self.note_correction(&key, index, CorrectionKind::Edit, original);
+        self.apply_correction(key, game_match);
+    }
+
+    /// Remove the last recorded game of a match
+    ///
+    /// The match of the players given by name and the `series` may be finished afterwards or not.
+    /// The removed game is kept in the audit trail.
+    ///
+    /// Only the owner and the trusted accounts may remove games.
+    pub fn remove_last_game(&mut self, player_names: &(String, String), series: u8) {
+        require!(
+            self.caller_is_allowed(),
+            "You may not correct games of the league"
+        );
+        self.assert_not_closed();
+        let (key, mut game_match, _swapped) = self.recorded_match(player_names, series);
+        let original = game_match.remove_last_game();
+        require!(original.is_some(), "Match has no game to remove");
+        let index = game_match.game_count() as u8;
+        self.note_correction(&key, index, CorrectionKind::Removal, original.unwrap());
+        self.apply_correction(key, game_match);
+    }
+
+    /// Find a recorded match by the names of the players and the `series`
+    ///
+    /// Returns the key, the match and whether the names are swapped compared to the key.
+    fn recorded_match(
+        &self,
+        player_names: &(String, String),
+        series: u8,
+    ) -> (MatchKey, GameMatch, bool) {
+        let pair = self.pair_of(player_names);
+        require!(
+            pair.is_some(),
+            "At least one player not found in the league"
+        );
+        let pair = pair.unwrap();
+        let swapped = self.players.get(pair.first as u64).unwrap() != player_names.0;
+        let key = MatchKey::new(pair, series);
+        let game_match = self.game_matches.get(&key);
+        require!(game_match.is_some(), "Match not found in the league");
+        (key, game_match.unwrap(), swapped)
+    }
+
+    /// Note a correction of the game at `index` in the audit trail
+    fn note_correction(&mut self, key: &MatchKey, index: u8, kind: CorrectionKind, original: Game) {
+        self.corrections.push(&Correction {
+            key: MatchKey::new(PlayerPair::new(key.pair.first, key.pair.second), key.series),
+            game: index,
+            kind,
+            original,
+            account: env::predecessor_account_id(),
+            timestamp: env::block_timestamp(),
+        });
+    }
+
+    /// Save a corrected match and check that the league still fits together
+    ///
+    /// A match without any game is removed, as if it was never started.
+    /// The group stage can be corrected until the first playoff match is recorded,
+    /// in that case the playoffs are seeded again from the corrected group standings.
+    fn apply_correction(&mut self, key: MatchKey, game_match: GameMatch) {
+        require!(!self.is_frozen(&key), "Match is frozen by an open dispute");
+        let offset = self.bracket_series_offset();
+        if key.series < offset {
+            require!(
+                self.game_matches.keys().all(|other| other.series < offset),
+                "The group stage cannot be corrected after the playoffs started"
+            );
+            self.phase = Phase::Regular;
+            self.playoff_seeds.clear();
+        }
+        require!(
+            !game_match.decided_early(game_match.best_of(), self.properties.get_draw_rule()),
+            "The match would be decided before its last game"
+        );
+        if game_match.game_count() == 0
+            && game_match.get_forfeit().is_none()
+            && game_match.get_pending().is_none()
+        {
+            self.game_matches.remove(&key);
+        } else {
+            self.game_matches.insert(&key, &game_match);
+        }
+        self.assert_consistent();
+        self.advance();
+    }
+
+    /// Check that the recorded matches still fit the format after a correction
+    ///
+    /// The later matches of a bracket depend on the results before,
+    /// so these results cannot change anymore once a later match was played.
+    /// The same goes for the earlier rounds of a swiss league, which have to stay finished.
+    fn assert_consistent(&self) {
+        if let Some(bracket) = self.bracket() {
+            let offset = self.bracket_series_offset();
+            let resolved = self.resolve_bracket(&bracket);
+            for key in self.game_matches.keys() {
+                // The group stage is not part of the bracket
+                if key.series < offset {
+                    continue;
+                }
+                require!(
+                    resolved.iter().any(|resolved_match| {
+                        resolved_match.is_between(key.pair.first, key.pair.second)
+                            && resolved_match.series + offset == key.series
+                    }),
+                    "A later match of the bracket depends on this result"
+                );
+            }
+        }
+        if let LeagueFormat::Swiss { .. } = self.properties.get_format() {
+            for round in self.rounds.iter().take(self.rounds.len() as usize - 1) {
+                require!(
+                    round.pairings.iter().all(|(a, b)| {
+                        self.game_matches
+                            .get(&MatchKey::new(PlayerPair::new(*a, *b), 0))
+                            .is_some_and(|game_match| self.match_winner(&game_match).exist())
+                    }),
+                    "Matches of earlier swiss rounds have to stay finished"
+                );
+            }
+        }
+    }
+
+    /// Open a dispute on a recorded match or one of its games
+    ///
+    /// The match is given by the names of the players and the `series`, the game by its index.
+    /// Without a game the whole match is disputed, e.g. a forfeit.
+    /// The match is frozen until a trusted account resolves the dispute.
+    ///
+    /// The players linked to the match and the trusted accounts may open disputes.
+    /// Returns the id of the dispute.
+    pub fn open_dispute(
+        &mut self,
+        player_names: &(String, String),
+        series: u8,
+        game: Option<u8>,
+        reason: String,
+        evidence: Option<String>,
+    ) -> u64 {
+        self.assert_not_closed();
+        let (key, game_match, _swapped) = self.recorded_match(player_names, series);
+        let caller = self.player_of(&env::predecessor_account_id());
+        require!(
+            self.caller_is_allowed()
+                || caller
+                    .is_some_and(|player| key.pair.first == player || key.pair.second == player),
+            "Only the players of the match and trusted accounts may open a dispute"
+        );
+        require!(
+            game.iter()
+                .all(|index| (*index as usize) < game_match.game_count()),
+            "Game not found in the match"
+        );
+        require!(!self.is_frozen(&key), "The match is already disputed");
+        self.disputes.push(&Dispute {
+            key,
+            game,
+            reason,
+            evidence,
+            opened_by: env::predecessor_account_id(),
+            opened_at: env::block_timestamp(),
+            resolution: None,
+        });
+        self.disputes.len() - 1
+    }
+
+    /// Resolve the open dispute with the `id` by a `DisputeRuling`
+    ///
+    /// Upholding keeps the result, overturning lets the other player win the disputed games
+    /// and a replay removes them, so the match goes on from there.
+    /// If an overturned game decides the match earlier, the games played after it are removed.
+    /// The changed games are kept in the audit trail of the corrections.
+    ///
+    /// Only the owner and the trusted accounts may resolve disputes.
+    pub fn resolve_dispute(&mut self, id: u64, ruling: DisputeRuling) {
+        require!(
+            self.caller_is_allowed(),
+            "You may not resolve disputes in the league"
+        );
+        self.assert_not_closed();
+        let dispute = self.disputes.get(id);
+        require!(dispute.is_some(), "Dispute not found in the league");
+        let mut dispute = dispute.unwrap();
+        require!(dispute.resolution.is_none(), "Dispute is already resolved");
+        dispute.resolution = Some(Resolution {
+            ruling,
+            resolved_by: env::predecessor_account_id(),
+            resolved_at: env::block_timestamp(),
+        });
+        self.disputes.replace(id, &dispute);
+
+        let key = dispute.key;
+        let mut game_match = self.game_matches.get(&key).unwrap();
+        // Either the disputed game or every game of the match
+        let disputed = match dispute.game {
+            Some(index) => vec![index],
+            None => (0..game_match.game_count() as u8).collect(),
+        };
+        match ruling {
+            DisputeRuling::Uphold => return,
+            DisputeRuling::Overturn => {
+                require!(
+                    dispute.game.is_none()
+                        || game_match.game(disputed[0] as usize).outcome() != GameOutcome::Draw,
+                    "A drawn game cannot be overturned"
+                );
+                for index in disputed {
+                    let overturned = game_match.game(index as usize).overturned();
+                    let original = game_match.replace_game(index as usize, overturned);
+                    self.note_correction(&key, index, CorrectionKind::Edit, original);
+                }
+                if dispute.game.is_none() {
+                    game_match.overturn_forfeit();
+                }
+                // The match may now be decided earlier, so the games after that no longer count
+                let draw_rule = self.properties.get_draw_rule();
+                while game_match.decided_early(game_match.best_of(), draw_rule) {
+                    let original = game_match.remove_last_game().unwrap();
+                    let index = game_match.game_count() as u8;
+                    self.note_correction(&key, index, CorrectionKind::Removal, original);
+                }
+            }
+            DisputeRuling::Replay => {
+                // The later games move up, so they are removed from the back
+                for index in disputed.into_iter().rev() {
+                    let original = game_match.remove_game(index as usize);
+                    self.note_correction(&key, index, CorrectionKind::Removal, original);
+                }
+                if dispute.game.is_none() {
+                    game_match.clear_forfeit();
+                }
+            }
+        }
+        self.apply_correction(key, game_match);
+    }
+
+    /// Check if the match is frozen by an open dispute
+    fn is_frozen(&self, key: &MatchKey) -> bool {
+        self.disputes
+            .iter()
+            .any(|dispute| dispute.resolution.is_none() && dispute.key == *key)
+    }
+
+    /// Withdraw a player from the league
+    ///
+    /// The `WithdrawalPolicy` of the league decides if the played matches of the player still count
+    /// and if the remaining ones are forfeited or not played at all.
+    /// The player stays in the list of players but cannot play anymore.
+    /// As this forfeits matches, the league has to be running and not paused.
+    ///
+    /// Only the owner may withdraw players.
+    pub fn withdraw_player(&mut self, name: &String) {
+        require!(
+            self.caller_is_owner(),
+            "Only the owner may withdraw players"
+        );
+        self.assert_running();
+        let index = self.players.iter().position(|player| player == *name);
+        require!(index.is_some(), "Player not found in the league");
+        let index = index.unwrap() as u8;
+        require!(
+            !self.withdrawn.contains(&index),
+            "Player has already withdrawn"
+        );
+        self.withdrawn.push(index);
+        self.advance();
+    }
+
+    /// Check if a withdrawn player takes part in the match with the `key`
+    fn involves_withdrawn(&self, key: &MatchKey) -> bool {
+        self.withdrawn.contains(&key.pair.first) || self.withdrawn.contains(&key.pair.second)
+    }
+
+    /// Check if the match with the `key` counts in the standings
+    ///
+    /// The matches of a withdrawn player are annulled if the league's policy says so.
+    fn counts(&self, key: &MatchKey) -> bool {
+        !(self.properties.get_withdrawal_policy().annul_played && self.involves_withdrawn(key))
+    }
+
+    /// Move the league on after a match was finished
+    ///
+    /// This pairs the next swiss round or starts the playoffs if it is time to.
+    /// Then the matches of withdrawn players which became playable are forfeited,
+    /// which may move the league on again.
+    fn advance(&mut self) {
+        loop {
+            self.pair_next_swiss_round();
+            self.start_playoffs();
+            if !self.forfeit_withdrawn_matches() {
+                return;
+            }
+        }
+    }
+
+    /// Forfeit the open matches of withdrawn players
+    ///
+    /// The opponent wins by walkover, or nobody if both withdrew.
+    /// Nothing happens if the league's policy drops these matches instead.
+    /// Returns whether any match was forfeited.
+    fn forfeit_withdrawn_matches(&mut self) -> bool {
+        if self.properties.get_withdrawal_policy().drop_remaining {
+            return false;
+        }
+        let mut forfeited = false;
+        for (key, stage, round) in self.open_matches() {
+            if !self.involves_withdrawn(&key) {
+                continue;
+            }
+            let mut game_match = match self.game_matches.get(&key) {
+                Some(game_match) => game_match,
+                None => GameMatch::new(self.resolve_best_of(&key.pair, key.series, stage, round)),
+            };
+            let winner = match (
+                self.withdrawn.contains(&key.pair.first),
+                self.withdrawn.contains(&key.pair.second),
+            ) {
+                (true, true) => Winner::NoWinner,
+                (true, false) => Winner::SecondPlayer,
+                _ => Winner::FirstPlayer,
+            };
+            game_match.forfeit(winner, ForfeitReason::Withdrawal);
+            self.game_matches.insert(&key, &game_match);
+            forfeited = true;
+        }
+        forfeited
+    }
+
+    /// Find the unfinished match the players given by name have to play right now
+    ///
+    /// The players have to be in the league and the format has to let them face each other.
+    /// Returns the key of the match, the match itself, which is new if nothing was played yet,
+    /// and whether the names are swapped compared to the key.
+    ///
+    /// Beware! This method can panic too!
+    fn current_match(&self, player_names: &(String, String)) -> (MatchKey, GameMatch, bool) {
+        self.assert_running();
+        // Wonderful iteration through all the names to find the correct indices
+        // Maybe it could be done more beautiful but I think this is well enough
+        let mut first: Option<u8> = None;
+        let mut second: Option<u8> = None;
+        for (idx, i) in self.players.iter().enumerate() {
+            if i == player_names.0 {
+                first = Some(idx as u8);
+            } else if i == player_names.1 {
+                second = Some(idx as u8);
+            }
+        }
+        require!(
+            first.is_some() && second.is_some(),
+            "At least one player not found in the league"
+        ); // Check if both indices exist
+        require!(
+            !self.withdrawn.contains(&first.unwrap()) && !self.withdrawn.contains(&second.unwrap()),
+            "Player has withdrawn from the league"
+        );
+
+        // In the group stage only players of the same group play against each other
+        if let LeagueFormat::GroupsAndPlayoffs { .. } = self.properties.get_format() {
+            let groups = self.properties.get_groups();
+            require!(
+                self.phase == Phase::Playoffs
+                    || groups[first.unwrap() as usize] == groups[second.unwrap() as usize],
+                "Players are not in the same group"
+            );
+        }
+
+        // In a swiss league only the pairings of the active round can be played
+        if let LeagueFormat::Swiss { .. } = self.properties.get_format() {
+            require!(
+                self.rounds
+                    .get(self.rounds.len() - 1)
+                    .unwrap()
+                    .pairs(first.unwrap(), second.unwrap()),
+                "Players are not paired in the active round"
+            );
+        }
+
+        let pair = PlayerPair::new(first.unwrap(), second.unwrap());
+        let swapped = pair.is_swapped(first.unwrap());
+
+        // The stage and the round of the match are needed for its best_of
+        let format = self.properties.get_format();
+        let (series, stage, round) = match self.bracket() {
+            None => match format {
+                // A round robin is played matchday by matchday
+                LeagueFormat::RoundRobin | LeagueFormat::DoubleRoundRobin => {
+                    let series = match format {
+                        LeagueFormat::DoubleRoundRobin => {
+                            self.next_leg(first.unwrap(), second.unwrap())
+                        }
+                        _ => 0,
+                    };
+                    let schedule = self.schedule().unwrap();
+                    let key =
+                        MatchKey::new(PlayerPair::new(first.unwrap(), second.unwrap()), series);
+                    let matchday = self.matchday_of(&schedule, &key);
+                    if self.properties.get_strict_schedule() {
+                        let current = self.current_matchday(&schedule);
+                        require!(
+                            matchday <= current.unwrap_or(matchday),
+                            "Match is on a future matchday"
+                        );
+                    }
+                    (series, Stage::Regular, Some(matchday as u8 + 1))
+                }
+                LeagueFormat::Swiss { .. } => (0, Stage::Regular, Some(self.rounds.len() as u8)),
+                _ => (0, Stage::Regular, None),
+            },
+            // In a knockout format only the open matches of the bracket can be played
+            Some(bracket) => {
+                let resolved = self.resolve_bracket(&bracket);
+                let open = resolved.iter().find(|resolved_match| {
+                    resolved_match.is_open()
+                        && resolved_match.is_between(first.unwrap(), second.unwrap())
+                });
+                require!(
+                    open.is_some(),
+                    "Players do not face each other in the current bracket"
+                );
+                let open = open.unwrap();
+                (
+                    open.series + self.bracket_series_offset(),
+                    Stage::from(open.section),
+                    Some(open.round),
+                )
+            }
+        };
+
+        let best_of = self.resolve_best_of(&pair, series, stage, round);
+        let key = MatchKey::new(pair, series);
+        let game_match = self.game_matches.get(&key);
+
+        let game_match = match game_match {
+            None => GameMatch::new(best_of),
+            Some(m) => m,
+        };
+        require!(!self.is_frozen(&key), "Match is frozen by an open dispute");
+        require!(
+            !self.match_winner(&game_match).exist(),
+            "Match is already finished"
+        ); // Check if the game match is already full (has a winner)
+        (key, game_match, swapped)
+    }
+
+    /// Seed the playoffs as soon as every group match is finished
+    ///
+    /// The best `advance` players of each group are seeded by their placement first and
+    /// their group second, e.g. A1, B1, A2, B2. This way group winners face runners-up of other groups.
+    /// Nothing happens in other formats or after the group stage.
+    fn start_playoffs(&mut self) {
+        let (groups, advance) = match self.properties.get_format() {
+            LeagueFormat::GroupsAndPlayoffs { groups, advance } => (groups, advance),
+            _ => return,
+        };
+        if self.phase != Phase::Regular || !self.active_matches_finished() {
+            return;
+        }
+
+        // Withdrawn players do not advance, so the next player of their group moves up
+        let tables = (0..groups)
+            .map(|group| {
+                self.group_ranking(group)
+                    .into_iter()
+                    .filter(|index| !self.withdrawn.contains(index))
+                    .collect()
+            })
+            .collect::<Vec<Vec<u8>>>();
+        let mut seeds = Vec::new();
+        for place in 0..advance as usize {
+            for table in tables.iter() {
+                seeds.extend(table.get(place));
+            }
+        }
+        self.playoff_seeds = seeds;
+        self.phase = Phase::Playoffs;
+    }
+
+    /// Compute the standings of a group from its matches in the group stage
+    pub fn group_standings(&self, group: u8) -> Vec<StandingsEntry> {
+        let members = self.group_members(group);
+        let local = |index: u8| members.iter().position(|member| *member == index);
+        let results = self
+            .game_matches
+            .iter()
+            .filter(|(key, _)| key.series == 0 && self.counts(key))
+            .filter_map(|(key, game_match)| {
+                let (first_games, second_games) = game_match.score();
+                Some(FinishedMatch {
+                    first: local(key.pair.first)? as u8,
+                    second: local(key.pair.second)? as u8,
+                    winner: self.match_winner(&game_match),
+                    forfeit: game_match.get_forfeit().is_some(),
+                    first_games,
+                    second_games,
+                })
+            })
+            .collect::<Vec<FinishedMatch>>();
+        standings::compute_standings(
+            members
+                .iter()
+                .map(|member| self.players.get(*member as u64).unwrap())
+                .collect(),
+            &results,
+            &[],
+            &self.properties.get_points(),
+            &self.properties.get_tiebreakers(),
+        )
+    }
+
+    /// The player indices of a group in the order of its standings
+    fn group_ranking(&self, group: u8) -> Vec<u8> {
+        let members = self.group_members(group);
+        let names = members
+            .iter()
+            .map(|member| self.players.get(*member as u64).unwrap())
+            .collect::<Vec<String>>();
+        self.group_standings(group)
+            .iter()
+            .map(|entry| members[names.iter().position(|n| *n == entry.player).unwrap()])
+            .collect()
+    }
+
+    /// Summarize the standings of every group for the views
+    ///
+    /// Returns `None` if the league has no groups.
+    pub fn summarize_groups(&self) -> Option<Vec<Vec<StandingsEntry>>> {
+        match self.properties.get_format() {
+            LeagueFormat::GroupsAndPlayoffs { groups, .. } => Some(
+                (0..groups)
+                    .map(|group| self.group_standings(group))
+                    .collect(),
+            ),
+            _ => None,
+        }
+    }
+
+    /// Pair the next round of a swiss league as soon as every match of the active round is finished
+    ///
+    /// The pairing follows the current standings and avoids rematches.
+    /// If there is no pairing without a rematch, the league ends after the active round.
+    /// Nothing happens in other formats or after the last round.
+    fn pair_next_swiss_round(&mut self) {
+        let rounds = match self.properties.get_format() {
+            LeagueFormat::Swiss { rounds } => rounds,
+            _ => return,
+        };
+        if self.rounds.len() >= rounds as u64 || !self.active_matches_finished() {
+            return;
+        }
+        if let Some(next) = self.next_swiss_round() {
+            self.rounds.push(&next);
+        }
+    }
+
+    /// Check if every match of the active swiss round or of the group stage is finished
+    ///
+    /// The dropped matches of withdrawn players are never finished, so they are not waited for.
+    fn active_matches_finished(&self) -> bool {
+        let dropped = self.properties.get_withdrawal_policy().drop_remaining;
+        self.open_matches()
+            .iter()
+            .all(|(key, _, _)| dropped && self.involves_withdrawn(key))
+    }
+
+    /// Pair the next swiss round by the current standings without any rematch
+    ///
+    /// Returns `None` if there is no such pairing.
+    fn next_swiss_round(&self) -> Option<SwissRound> {
+        // Withdrawn players are not paired anymore
+        let players = self.players.to_vec();
+        let ranking = self
+            .standings()
+            .iter()
+            .map(|entry| players.iter().position(|p| *p == entry.player).unwrap() as u8)
+            .filter(|index| !self.withdrawn.contains(index))
+            .collect::<Vec<u8>>();
+        swiss::next_round(&ranking, &self.byes(), |a, b| {
+            self.game_matches
+                .get(&MatchKey::new(PlayerPair::new(a, b), 0))
+                .is_some()
+        })
+    }
+
+    /// The players who got a bye, once for every bye
+    fn byes(&self) -> Vec<u8> {
+        self.rounds.iter().filter_map(|round| round.bye).collect()
+    }
+
+    /// Compute the standings of the league
+    ///
+    /// Only finished matches are taken into account.
+    /// Every match counts, so with groups the playoff matches are added to the group matches.
+    pub fn standings(&self) -> Vec<StandingsEntry> {
+        let results = self
+            .game_matches
+            .iter()
+            .filter(|(key, _)| self.counts(key))
+            .map(|(key, game_match)| {
+                let (first_games, second_games) = game_match.score();
+                FinishedMatch {
+                    first: key.pair.first,
+                    second: key.pair.second,
+                    winner: self.match_winner(&game_match),
+                    forfeit: game_match.get_forfeit().is_some(),
+                    first_games,
+                    second_games,
+                }
+            })
+            .collect::<Vec<FinishedMatch>>();
+        let mut entries = standings::compute_standings(
+            self.players.to_vec(),
+            &results,
+            &self.byes(),
+            &self.properties.get_points(),
+            &self.properties.get_tiebreakers(),
+        );
+        for entry in entries.iter_mut() {
+            entry.withdrawn = self
+                .withdrawn
+                .iter()
+                .any(|index| self.players.get(*index as u64).unwrap() == entry.player);
+        }
+        entries
+    }
+
+    /// Summarize the bracket for the views
+    ///
+    /// Returns `None` if the league is not played in a knockout format.
+    /// The bracket of the playoffs is empty as long as the groups are played.
+    pub fn summarize_bracket(&self) -> Option<Vec<HelperBracketMatch>> {
+        // The bracket is seeded once the league starts
+        if !self.has_started() {
+            return self.properties.get_format().is_knockout().then(Vec::new);
+        }
+        if let LeagueFormat::GroupsAndPlayoffs { .. } = self.properties.get_format() {
+            if self.phase == Phase::Regular {
+                return Some(Vec::new());
+            }
+        }
+        let bracket = self.bracket()?;
+        let occupant = |occupant: Occupant| match occupant {
+            Occupant::Player(index) => {
+                HelperOccupant::Player(self.players.get(index as u64).unwrap())
+            }
+            Occupant::Bye => HelperOccupant::Bye,
+            Occupant::Pending => HelperOccupant::Pending,
+        };
+        Some(
+            self.resolve_bracket(&bracket)
+                .into_iter()
+                .map(|resolved_match| HelperBracketMatch {
+                    section: resolved_match.section,
+                    round: resolved_match.round,
+                    first: occupant(resolved_match.first),
+                    second: occupant(resolved_match.second),
+                    winner: occupant(resolved_match.winner),
+                })
+                .collect(),
+        )
+    }
+
+    /// Summarize the rounds for the views
+    ///
+    /// Returns `None` if the league is not played in the swiss format.
+    pub fn summarize_rounds(&self) -> Option<Vec<HelperSwissRound>> {
+        if !matches!(self.properties.get_format(), LeagueFormat::Swiss { .. }) {
+            return None;
+        }
+        let name = |index: u8| self.players.get(index as u64).unwrap();
+        Some(
+            self.rounds
+                .iter()
+                .enumerate()
+                .map(|(index, round)| HelperSwissRound {
+                    round: index as u8 + 1,
+                    pairings: round
+                        .pairings
+                        .iter()
+                        .map(|(a, b)| (name(*a), name(*b)))
+                        .collect(),
+                    bye: round.bye.map(name),
+                })
+                .collect(),
+        )
+    }
+
+    /// The leg of a double round robin which is played next by the players `a` and `b`
+    ///
+    /// The legs are played one after another, the second starts as soon as the first is decided.
+    /// So the order of the names only orients the outcome and never picks the leg.
+    fn next_leg(&self, a: u8, b: u8) -> u8 {
+        self.game_matches
+            .get(&MatchKey::new(PlayerPair::new(a, b), 0))
+            .is_some_and(|first_leg| self.match_winner(&first_leg).exist()) as u8
+    }
+
+    /// Summarize the matchdays for the views
+    ///
+    /// Returns `None` if the league is not played in a round robin format.
+    pub fn summarize_schedule(&self) -> Option<Vec<HelperMatchday>> {
+        // The matchdays are generated once the league starts
+        if !self.has_started() {
+            return self.properties.get_format().has_schedule().then(Vec::new);
+        }
+        let name = |index: u8| self.players.get(index as u64).unwrap();
+        Some(
+            self.schedule()?
+                .into_iter()
+                .enumerate()
+                .map(|(index, matchday)| HelperMatchday {
+                    matchday: index as u8 + 1,
+                    pairings: matchday
+                        .pairings
+                        .iter()
+                        .map(|(home, away)| (name(*home), name(*away)))
+                        .collect(),
+                    bye: matchday.bye.map(name),
+                    deadline: self.deadline(Some(index as u8 + 1)).map(U64),
+                })
+                .collect(),
+        )
+    }
+
+    /// Summarize the audit trail of the corrections for the views
+    pub fn summarize_corrections(&self) -> Vec<HelperCorrection> {
+        let game_type = self.properties.get_game_type();
+        self.corrections
+            .iter()
+            .map(|correction| HelperCorrection {
+                first_player: self.players.get(correction.key.pair.first as u64).unwrap(),
+                second_player: self.players.get(correction.key.pair.second as u64).unwrap(),
+                series: correction.key.series,
+                game: correction.game,
+                kind: correction.kind,
+                original: HelperGame {
+                    outcome: correction.original.outcome(),
+                    data: correction.original.game_content(&game_type),
+                },
+                account: correction.account,
+                timestamp: U64(correction.timestamp),
+            })
+            .collect()
+    }
+
+    /// Summarize every dispute with its resolution for the views
+    pub fn summarize_disputes(&self) -> Vec<HelperDispute> {
+        self.disputes
+            .iter()
+            .enumerate()
+            .map(|(id, dispute)| HelperDispute {
+                id: id as u64,
+                first_player: self.players.get(dispute.key.pair.first as u64).unwrap(),
+                second_player: self.players.get(dispute.key.pair.second as u64).unwrap(),
+                series: dispute.key.series,
+                game: dispute.game,
+                reason: dispute.reason,
+                evidence: dispute.evidence,
+                opened_by: dispute.opened_by,
+                opened_at: U64(dispute.opened_at),
+                resolution: dispute.resolution.map(|resolution| HelperResolution {
+                    ruling: resolution.ruling,
+                    resolved_by: resolution.resolved_by,
+                    resolved_at: U64(resolution.resolved_at),
+                }),
+            })
+            .collect()
+    }
+
+    /// Summarize the league for the views
+    ///
+    /// Well, maybe I should just call it serialize?
+    /// But this gets also annoying as every ser/de lib calls some generated functions like that
+    pub fn summarize_league(&self) -> HelperLeague {
+        let format = self.properties.get_format();
+        let matches = self
+            .game_matches
+            .iter()
+            .map(|(key, game_match)| HelperMatch {
+                first_player: self.players.get(key.pair.first as u64).unwrap(),
+                second_player: self.players.get(key.pair.second as u64).unwrap(),
+                series: key.series,
+                leg: match format {
+                    LeagueFormat::DoubleRoundRobin => Some(key.series + 1),
+                    _ => None,
+                },
+                best_of: game_match.best_of(),
+                winner: self.match_winner(&game_match),
+                forfeit: game_match
+                    .get_forfeit()
+                    .map(|forfeit| forfeit.reason.clone()),
+                annulled: !self.counts(&key),
+                pending: game_match.get_pending().map(|pending| HelperPendingGame {
+                    reported_by: self.players.get(pending.reporter as u64).unwrap(),
+                    game: HelperGame {
+                        outcome: pending.game.outcome(),
+                        data: pending.game.game_content(&self.properties.get_game_type()),
+                    },
+                }),
+                games: game_match.summarize_match(self.properties.get_game_type()),
+            })
+            .collect::<Vec<HelperMatch>>();
+        HelperLeague {
+            owner: self.owner.clone(),
+            proposed_owner: self.proposed_owner.clone(),
+            format,
+            lifecycle: self.lifecycle,
+            registration: self.properties.get_registration(),
+            phase: self.phase,
+            best_of: self.properties.get_best_of(),
+            draw_rule: self.properties.get_draw_rule(),
+            withdrawn: self
+                .withdrawn
+                .iter()
+                .map(|index| self.players.get(*index as u64).unwrap())
+                .collect(),
+            players: self.players.to_vec(),
+            linked_accounts: self
+                .player_accounts
+                .iter()
+                .map(|(player, account_id)| {
+                    (
+                        self.players.get(*player as u64).unwrap(),
+                        account_id.clone(),
+                    )
+                })
+                .collect(),
+            link_requests: self
+                .link_requests
+                .iter()
+                .map(|(player, account_id)| {
+                    (
+                        self.players.get(*player as u64).unwrap(),
+                        account_id.clone(),
+                    )
+                })
+                .collect(),
+            matches,
+        }
+    }
+}
+
+/// Helper struct for serialization of a league in the views
+#[derive(Serialize)]
+pub struct HelperLeague {
+    pub owner: AccountId,
+    pub proposed_owner: Option<AccountId>,
+    pub format: LeagueFormat,
+    pub lifecycle: Lifecycle,
+    /// The cap and the deadline of the registration if the league has one
+    pub registration: Option<Registration>,
+    pub phase: Phase,
+    pub best_of: u8,
+    pub draw_rule: DrawRule,
+    /// The players in the order they were given or registered
+    pub players: Vec<String>,
+    /// The players who withdrew from the league
+    pub withdrawn: Vec<String>,
+    /// The accounts linked to the players
+    pub linked_accounts: Vec<(String, AccountId)>,
+    /// The accounts players asked to link, which the owner or a trusted account has to link
+    pub link_requests: Vec<(String, AccountId)>,
+    pub matches: Vec<HelperMatch>,
+}
+
+/// Helper struct for serialization of the pledge of a league in the views
+#[derive(Serialize)]
+pub struct HelperPledge {
+    /// The deposit of the league
+    pub pledge: U128,
+    /// The storage used by the league in bytes
+    pub storage_usage: StorageUsage,
+    /// The part of the pledge paying for the used storage
+    pub storage_cost: U128,
+}
+
+/// Helper struct for serialization of a match in the views
+#[derive(Serialize)]
+pub struct HelperMatch {
+    pub first_player: String,
+    pub second_player: String,
+    /// How often both players met before this match
+    pub series: u8,
+    /// The leg of a double round robin, the first player is at home in leg 1
+    pub leg: Option<u8>,
+    /// The best_of this match is played with, which may differ from the league's
+    pub best_of: u8,
+    pub winner: Winner,
+    /// Why the match was forfeited, if it was
+    pub forfeit: Option<ForfeitReason>,
+    /// Whether the match does not count as a player withdrew
+    pub annulled: bool,
+    /// The game reported by a player, which does not count until the opponent confirms it
+    pub pending: Option<HelperPendingGame>,
+    pub games: Vec<HelperGame>,
+}
+
+/// Helper struct for serialization of a pending game in the views
+#[derive(Serialize)]
+pub struct HelperPendingGame {
+    /// The player who reported the game
+    pub reported_by: String,
+    pub game: HelperGame,
+}
+
+/// Helper struct for serialization of a correction in the views
+#[derive(Serialize)]
+pub struct HelperCorrection {
+    pub first_player: String,
+    pub second_player: String,
+    pub series: u8,
+    /// The index of the game inside its match
+    pub game: u8,
+    pub kind: CorrectionKind,
+    /// The game as it was before the correction
+    pub original: HelperGame,
+    /// Who corrected the game
+    pub account: AccountId,
+    /// When the game was corrected as block timestamp in nanoseconds
+    pub timestamp: U64,
+}
+
+/// Helper struct for serialization of a dispute in the views
+#[derive(Serialize)]
+pub struct HelperDispute {
+    pub id: u64,
+    pub first_player: String,
+    pub second_player: String,
+    pub series: u8,
+    /// The index of the disputed game or none if the whole match is disputed
+    pub game: Option<u8>,
+    pub reason: String,
+    /// A reference to the evidence, e.g. a link to a replay or a screenshot
+    pub evidence: Option<String>,
+    pub opened_by: AccountId,
+    /// When the dispute was opened as block timestamp in nanoseconds
+    pub opened_at: U64,
+    /// How the dispute was resolved, none while it is open
+    pub resolution: Option<HelperResolution>,
+}
+
+/// Helper struct for serialization of the resolution of a dispute in the views
+#[derive(Serialize)]
+pub struct HelperResolution {
+    pub ruling: DisputeRuling,
+    pub resolved_by: AccountId,
+    /// When the dispute was resolved as block timestamp in nanoseconds
+    pub resolved_at: U64,
+}
+
+/// Helper struct for serialization of a game in the views
+///
+/// The data is nested as it is, its structure is given by the league's `GameType`
+#[derive(Serialize)]
+pub struct HelperGame {
+    pub outcome: GameOutcome,
+    pub data: Value,
+}
+
+/// A correction of a recorded game kept for the audit trail
+#[derive(BorshDeserialize, BorshSerialize)]
+pub struct Correction {
+    /// The match of the corrected game
+    key: MatchKey,
+    /// The index of the game inside its match
+    game: u8,
+    kind: CorrectionKind,
+    /// The game as it was before the correction
+    original: Game,
+    /// Who corrected the game
+    account: AccountId,
+    /// The block timestamp in nanoseconds
+    timestamp: u64,
+}
+
+/// A dispute of a recorded match or one of its games
+#[derive(BorshDeserialize, BorshSerialize)]
+pub struct Dispute {
+    /// The disputed match
+    key: MatchKey,
+    /// The index of the disputed game or none if the whole match is disputed
+    game: Option<u8>,
+    reason: String,
+    /// A reference to the evidence, e.g. a link to a replay or a screenshot
+    evidence: Option<String>,
+    opened_by: AccountId,
+    /// The block timestamp in nanoseconds
+    opened_at: u64,
+    /// How the dispute was resolved, none while it is open
+    resolution: Option<Resolution>,
+}
+
+/// The resolution of a dispute
+#[derive(BorshDeserialize, BorshSerialize)]
+pub struct Resolution {
+    ruling: DisputeRuling,
+    resolved_by: AccountId,
+    /// The block timestamp in nanoseconds
+    resolved_at: u64,
+}
+
+/// How a dispute is resolved
+#[derive(
+    BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone, Copy, PartialEq, Debug,
+)]
+pub enum DisputeRuling {
+    /// The disputed result stands
+    Uphold,
+    /// The other player wins the disputed games, a forfeit goes to the other player as well
+    Overturn,
+    /// The disputed games are removed and have to be played again, a forfeit is lifted
+    Replay,
+}
+
+/// How a recorded game was corrected
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Clone, Copy, PartialEq, Debug)]
+pub enum CorrectionKind {
+    /// The outcome or the data of the game was changed
+    Edit,
+    /// The game was removed from its match
+    Removal,
+}
+
+/// Return the games won by each player in the `games`
+///
+/// Drawn games are not counted for anybody.
+fn score_of(games: &[Game]) -> (u8, u8) {
+    let mut a = 0;
+    let mut b = 0;
+    for game in games.iter() {
+        match game.outcome() {
+            GameOutcome::FirstPlayerWon => a += 1,
+            GameOutcome::SecondPlayerWon => b += 1,
+            GameOutcome::Draw => (),
+        }
+    }
+    (a, b)
+}
+
+/// The forfeit deciding a `GameMatch`
+#[derive(BorshDeserialize, BorshSerialize)]
+pub struct Forfeit {
+    /// The player winning by walkover or `NoWinner` if both forfeited
+    pub winner: Winner,
+    pub reason: ForfeitReason,
+}
+
+/// A game reported by a player of the match
+///
+/// The outcome and the data are in the order of the `GameMatch` like every other game.
+#[derive(BorshDeserialize, BorshSerialize)]
+pub struct PendingGame {
+    pub game: Game,
+    /// The index of the player who reported the game
+    pub reporter: u8,
+}
+
+/// Why a match was forfeited
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Clone, PartialEq, Debug)]
+pub enum ForfeitReason {
+    /// The match was not finished before its deadline
+    Deadline,
+    /// A player withdrew from the league
+    Withdrawal,
+    /// A trusted account recorded the forfeit with the given reason, e.g. a no-show
+    Recorded(String),
+}
+
+/// How overdue matches are forfeited
+#[derive(
+    BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone, Copy, PartialEq, Default,
+)]
+pub enum ForfeitPolicy {
+    /// Both players lose the match
+    #[default]
+    DoubleForfeit,
+    /// The player ahead in games wins by walkover, if nobody is ahead both lose
+    ///
+    /// So the walkover only decides a match which was partially played.
+    /// A match without any game is lost by both like with `DoubleForfeit`.
+    Walkover,
+}
+
+/// What happens with the matches of a player who withdraws
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone, Copy, Default)]
+#[serde(default)]
+pub struct WithdrawalPolicy {
+    /// The matches the player played do not count in the standings anymore
+    pub annul_played: bool,
+    /// The remaining matches of the player are not played at all instead of being forfeited.
+    /// Knockout formats cannot do this, as every match of the bracket needs somebody advancing.
+    pub drop_remaining: bool,
+}
+
+/// The upgradeable enum for the properties to be able to easily upgrade the league
+///
+/// The old version is a lot smaller than the current one, which is fine as only one of them is stored.
+#[allow(clippy::large_enum_variant)]
+#[derive(BorshDeserialize, BorshSerialize)]
+pub enum UpgradeableLeagueProperties {
+    V1(LeaguePropertiesV1),
+    V2(LeagueProperties),
+}
+
+/// First version of the properties
+///
+/// It is kept to read the leagues created before drawn games were possible.
+#[derive(BorshDeserialize, BorshSerialize, Clone)]
+pub struct LeaguePropertiesV1 {
+    /// The maximum amount of games each `GameMatch` may have
+    pub best_of: u8,
+    /// The actual type of the game which is played.
+    pub game_type: GameType,
+}
+
+/// Current version of the properties
+#[derive(BorshDeserialize, BorshSerialize)]
+pub struct LeagueProperties {
+    /// The maximum amount of games each `GameMatch` may have
+    pub best_of: u8,
+    /// The actual type of the game which is played.
+    pub game_type: GameType,
+    /// How drawn games count in a `GameMatch`
+    pub draw_rule: DrawRule,
+    /// The points for a match in the standings
+    pub points: PointsScheme,
+    /// The order of the criteria for players with equal points
+    pub tiebreakers: Vec<Tiebreaker>,
+    /// Who plays against whom
+    pub format: LeagueFormat,
+    /// The group of every player in a league with groups, empty otherwise
+    pub groups: Vec<u8>,
+    /// The rules where `best_of` differs, e.g. for the final
+    pub best_of_overrides: Vec<BestOfOverride>,
+    /// Whether a round robin has to be played matchday by matchday
+    pub strict_schedule: bool,
+    /// The block timestamp in nanoseconds when every match has to be finished
+    pub deadline: Option<u64>,
+    /// The block timestamps in nanoseconds when the matchdays of a round robin have to be finished
+    pub matchday_deadlines: Vec<u64>,
+    /// How matches are forfeited after their deadline
+    pub forfeit_policy: ForfeitPolicy,
+    /// What happens with the matches of a withdrawn player
+    pub withdrawal_policy: WithdrawalPolicy,
+    /// The limits of the registration if the players may join by themselves
+    pub registration: Option<Registration>,
+}
+
+/// Optional settings of a league given on creation
+///
+/// Every field has a default, so a caller only needs to give what differs.
+#[derive(Serialize, Deserialize, Default)]
+#[serde(default)]
+pub struct LeagueOptions {
+    /// How drawn games count in a `GameMatch`.
+    /// If not given, knockout formats replay them and the others count half a point.
+    pub draw_rule: Option<DrawRule>,
+    /// The points for a match in the standings, 3/1/0 per default
+    pub points: PointsScheme,
+    /// The order of the criteria for players with equal points.
+    /// If not given, it depends on the format.
+    pub tiebreakers: Option<Vec<Tiebreaker>>,
+    /// Who plays against whom, a round robin per default
+    pub format: LeagueFormat,
+    /// The group of every player in the order of the players if the format has groups.
+    /// If not given, the players are distributed in snake order, e.g. A, B, B, A, A, B.
+    pub groups: Option<Vec<u8>>,
+    /// The rules where `best_of` differs from the league's, none per default
+    pub best_of_overrides: Vec<BestOfOverride>,
+    /// Whether a round robin has to be played matchday by matchday, off per default.
+    /// Then no match of a later matchday can be played before the current one is finished.
+    pub strict_schedule: bool,
+    /// The block timestamp in nanoseconds when every match has to be finished, none per default
+    pub deadline: Option<U64>,
+    /// The block timestamps in nanoseconds when each matchday of a round robin has to be finished.
+    /// A matchday without an entry falls back to `deadline`.
+    pub matchday_deadlines: Vec<U64>,
+    /// How matches are forfeited after their deadline, both lose per default
+    pub forfeit_policy: ForfeitPolicy,
+    /// What happens with the matches of a withdrawn player.
+    /// Per default the played ones count and the remaining ones are forfeited.
+    pub withdrawal_policy: WithdrawalPolicy,
+    /// Whether the league starts in registration, where accounts join by themselves, none per default.
+    /// Then the league has to be started by the owner.
+    pub registration: Option<Registration>,
+    /// Whether the league is created as a draft, off per default.
+    /// Then the owner opens the registration or starts the league when it is prepared.
+    pub draft: bool,
+}
+
+/// The limits of the registration of a league
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone, Default)]
+#[serde(default)]
+pub struct Registration {
+    /// The maximum number of players, none per default
+    pub cap: Option<u8>,
+    /// The block timestamp in nanoseconds until accounts may join, none per default
+    pub deadline: Option<U64>,
+}
+
+/// The stage of a league a match is played in
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone, Copy, PartialEq)]
+pub enum Stage {
+    /// Every match outside of a bracket, e.g. the round robin, the swiss rounds or the group stage
+    Regular,
+    /// The upper bracket of a knockout format, i.e. the whole single elimination bracket or the playoffs
+    Upper,
+    /// The lower bracket of a double elimination
+    Lower,
+    /// The grand final of a double elimination including the reset
+    GrandFinal,
+}
+
+impl From<BracketSection> for Stage {
+    fn from(section: BracketSection) -> Self {
+        match section {
+            BracketSection::Upper => Stage::Upper,
+            BracketSection::Lower => Stage::Lower,
+            BracketSection::GrandFinal => Stage::GrandFinal,
+        }
+    }
+}
+
+/// A rule to play some matches with another `best_of` than the league's
+///
+/// If several rules fit a match, the most specific one wins, i.e. a match over a round over a stage.
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone)]
+pub enum BestOfOverride {
+    /// Every match in the `stage`
+    Stage { stage: Stage, best_of: u8 },
+    /// Every match in the `round` of the `stage`, starting with 1.
+    /// The rounds are the ones of the bracket section, the swiss rounds or the matchdays of a round robin.
+    Round {
+        stage: Stage,
+        round: u8,
+        best_of: u8,
+    },
+    /// The match between both `players` in any order.
+    /// `series` tells which meeting of both is meant, 0 for the first one.
+    Match {
+        players: (String, String),
+        #[serde(default)]
+        series: u8,
+        best_of: u8,
+    },
+}
+
+impl BestOfOverride {
+    /// The `best_of` of the rule
+    pub fn best_of(&self) -> u8 {
+        match self {
+            BestOfOverride::Stage { best_of, .. }
+            | BestOfOverride::Round { best_of, .. }
+            | BestOfOverride::Match { best_of, .. } => *best_of,
+        }
+    }
+
+    /// Check if the rule is valid for a league with the given `players`
+    pub fn assert_valid(&self, players: &[String]) {
+        require!(self.best_of() % 2 == 1, "best_of number should be odd");
+        if let BestOfOverride::Match {
+            players: (a, b), ..
+        } = self
+        {
+            require!(
+                players.contains(a) && players.contains(b) && a != b,
+                "A best_of override needs two different players of the league"
+            );
+        }
+    }
+}
+
+/// The format of a league deciding who plays against whom
+#[derive(
+    BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone, Copy, PartialEq, Default,
+)]
+pub enum LeagueFormat {
+    /// Everybody plays against everybody once
+    #[default]
+    RoundRobin,
+    /// Everybody plays against everybody twice, once at home and once away.
+    /// The player earlier in the list of players is at home in the first leg.
+    DoubleRoundRobin,
+    /// A knockout bracket seeded by the order of the players.
+    /// Byes fill the bracket up to the next power of two.
+    SingleElimination,
+    /// A knockout bracket where a player is only out after the second lost match.
+    /// The losers drop into a lower bracket whose champion faces the upper champion in the grand final.
+    /// With `bracket_reset` the grand final is played again if the upper champion lost it.
+    DoubleElimination { bracket_reset: bool },
+    /// A fixed number of `rounds` where players with similar scores are paired without rematches.
+    /// With an odd number of players somebody gets a bye, which counts as a won match.
+    Swiss { rounds: u8 },
+    /// A round robin in each of the `groups` followed by single elimination playoffs.
+    /// The best `advance` players of every group are seeded into the playoffs.
+    GroupsAndPlayoffs { groups: u8, advance: u8 },
+}
+
+/// Where a league is in its life
+///
+/// A league goes from a draft over the registration to running and is finally closed and archived.
+/// The registration is skipped if the players are given on creation.
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Clone, Copy, PartialEq, Debug)]
+pub enum Lifecycle {
+    /// The league is prepared by the owner, nobody can join or play yet
+    Draft,
+    /// Accounts join the league until the owner starts it
+    Registration,
+    /// The roster is frozen and the matches are played
+    Running,
+    /// No matches can be played, but games can be corrected and disputes resolved
+    Paused,
+    /// The league is closed and its results do not change anymore
+    Finished,
+    /// Nothing changes anymore, not even the trusted accounts or the owner
+    Archived,
+}
+
+impl Lifecycle {
+    /// Check if a league may go from this state to the `next` one
+    pub fn may_become(self, next: Lifecycle) -> bool {
+        matches!(
+            (self, next),
+            (Lifecycle::Draft, Lifecycle::Registration)
+                | (Lifecycle::Draft, Lifecycle::Running)
+                | (Lifecycle::Registration, Lifecycle::Running)
+                | (Lifecycle::Running, Lifecycle::Paused)
+                | (Lifecycle::Paused, Lifecycle::Running)
+                | (Lifecycle::Running, Lifecycle::Finished)
+                | (Lifecycle::Paused, Lifecycle::Finished)
+                | (Lifecycle::Finished, Lifecycle::Archived)
+        )
+    }
+}
+
+/// The phase of a league
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Clone, Copy, PartialEq)]
+pub enum Phase {
+    /// The matches are played as given by the format, e.g. the group stage
+    Regular,
+    /// The knockout bracket after the group stage
+    Playoffs,
+}
+
+impl LeagueFormat {
+    /// Check if the format has a knockout bracket
+    pub fn is_knockout(&self) -> bool {
+        matches!(
+            self,
+            LeagueFormat::SingleElimination
+                | LeagueFormat::DoubleElimination { .. }
+                | LeagueFormat::GroupsAndPlayoffs { .. }
+        )
+    }
+
+    /// The draw rule if the league does not give any
+    ///
+    /// A knockout match cannot end in a draw, so its drawn games are replayed.
+    pub fn default_draw_rule(&self) -> DrawRule {
+        if self.is_knockout() {
+            DrawRule::Replay
+        } else {
+            DrawRule::HalfPoint
+        }
+    }
+
+    /// Check if the format is played in matchdays
+    pub fn has_schedule(&self) -> bool {
+        matches!(
+            self,
+            LeagueFormat::RoundRobin | LeagueFormat::DoubleRoundRobin
+        )
+    }
+
+    /// Generate the matchdays for the number of `players` if the format is played in matchdays
+    pub fn schedule(&self, players: u8) -> Option<Vec<Matchday>> {
+        match self {
+            LeagueFormat::RoundRobin => Some(schedule::round_robin(players)),
+            LeagueFormat::DoubleRoundRobin => Some(schedule::double_round_robin(players)),
+            _ => None,
+        }
+    }
+
+    /// The tiebreakers if the league does not give any
+    pub fn default_tiebreakers(&self) -> Vec<Tiebreaker> {
+        match self {
+            LeagueFormat::Swiss { .. } => standings::swiss_tiebreakers(),
+            _ => standings::default_tiebreakers(),
+        }
+    }
+
+    /// Check if the format can be played by the number of `players` with the `draw_rule`
+    pub fn assert_valid(&self, players: usize, draw_rule: DrawRule) {
+        require!(
+            !self.is_knockout() || draw_rule == DrawRule::Replay,
+            "Knockout formats need the Replay draw rule"
+        );
+        // With more rounds the pairing could run out of opponents who are no rematch
+        if let LeagueFormat::Swiss { rounds } = self {
+            require!(
+                *rounds > 0 && *rounds as usize <= players / 2,
+                "A swiss league needs at least one round and at most one round per two players"
+            );
+        }
+    }
+
+    /// Check the `groups` of the `players` or distribute them if none are given
+    ///
+    /// Returns an empty list if the format has no groups.
+    pub fn assign_groups(&self, players: usize, groups: Option<Vec<u8>>) -> Vec<u8> {
+        let (count, advance) = match self {
+            LeagueFormat::GroupsAndPlayoffs { groups, advance } => (*groups, *advance),
+            _ => {
+                require!(
+                    groups.is_none(),
+                    "Only a league with groups can assign groups"
+                );
+                return Vec::new();
+            }
+        };
+        require!(
+            count > 1 && advance > 0,
+            "A league with groups needs at least two groups and one advancing player per group"
+        );
+        let groups = groups.unwrap_or_else(|| {
+            (0..players)
+                .map(|index| {
+                    let position = (index % count as usize) as u8;
+                    if (index / count as usize) % 2 == 1 {
+                        count - 1 - position
+                    } else {
+                        position
+                    }
+                })
+                .collect()
+        });
+        require!(
+            groups.len() == players && groups.iter().all(|group| *group < count),
+            "Every player needs to be assigned to one of the groups"
+        );
+        for group in 0..count {
+            let size = groups.iter().filter(|g| **g == group).count();
+            require!(
+                size > 1 && size >= advance as usize,
+                "Every group needs at least two players and at least as many as advance"
+            );
+        }
+        groups
+    }
+}
+
+/// The rule how a drawn game counts in a best-of
+#[derive(
+    BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone, Copy, PartialEq, Default,
+)]
+pub enum DrawRule {
+    /// Both players get half a point.
+    /// A match where both have the same score after all games is drawn.
+    #[default]
+    HalfPoint,
+    /// The game does not count and has to be replayed.
+    /// A match always has a winner.
+    Replay,
+}
+
+impl From<LeaguePropertiesV1> for LeagueProperties {
+    /// The old leagues had no draws, replaying them keeps the way their matches were counted.
+    /// The settings added later take the defaults of a round robin.
+    fn from(prop: LeaguePropertiesV1) -> Self {
+        let format = LeagueFormat::RoundRobin;
+        LeagueProperties {
+            best_of: prop.best_of,
+            game_type: prop.game_type,
+            draw_rule: DrawRule::Replay,
+            points: PointsScheme::default(),
+            tiebreakers: format.default_tiebreakers(),
+            format,
+            groups: Default::default(),
+            best_of_overrides: Default::default(),
+            strict_schedule: Default::default(),
+            deadline: Default::default(),
+            matchday_deadlines: Default::default(),
+            forfeit_policy: Default::default(),
+            withdrawal_policy: Default::default(),
+            registration: Default::default(),
+        }
+    }
+}
+
+impl UpgradeableLeagueProperties {
+    /// Convenient implementation to get the `best_of` value independant of the `LeagueProperties` version
+    pub fn get_best_of(&self) -> u8 {
+        match self {
+            UpgradeableLeagueProperties::V1(prop) => prop.best_of,
+            UpgradeableLeagueProperties::V2(prop) => prop.best_of,
+        }
+    }
+
+    /// Convenient implementation to get the `game_type` value independant of the `LeagueProperties` version
+    pub fn get_game_type(&self) -> GameType {
+        match self {
+            UpgradeableLeagueProperties::V1(prop) => prop.game_type.clone(),
+            UpgradeableLeagueProperties::V2(prop) => prop.game_type.clone(),
+        }
+    }
+
+    /// Convenient implementation to get the `draw_rule` value independant of the `LeagueProperties` version
+    pub fn get_draw_rule(&self) -> DrawRule {
+        match self {
+            UpgradeableLeagueProperties::V1(_) => DrawRule::Replay,
+            UpgradeableLeagueProperties::V2(prop) => prop.draw_rule,
+        }
+    }
+
+    /// Convenient implementation to get the `points` value independant of the `LeagueProperties` version
+    pub fn get_points(&self) -> PointsScheme {
+        match self {
+            UpgradeableLeagueProperties::V1(prop) => LeagueProperties::from(prop.clone()).points,
+            UpgradeableLeagueProperties::V2(prop) => prop.points.clone(),
+        }
+    }
+
+    /// Convenient implementation to get the `tiebreakers` value independant of the `LeagueProperties` version
+    pub fn get_tiebreakers(&self) -> Vec<Tiebreaker> {
+        match self {
+            UpgradeableLeagueProperties::V1(prop) => {
+                LeagueProperties::from(prop.clone()).tiebreakers
+            }
+            UpgradeableLeagueProperties::V2(prop) => prop.tiebreakers.clone(),
+        }
+    }
+
+    /// Convenient implementation to get the `groups` value independant of the `LeagueProperties` version
+    pub fn get_groups(&self) -> Vec<u8> {
+        match self {
+            UpgradeableLeagueProperties::V1(prop) => LeagueProperties::from(prop.clone()).groups,
+            UpgradeableLeagueProperties::V2(prop) => prop.groups.clone(),
+        }
+    }
+
+    /// Convenient implementation to get the `best_of_overrides` value independant of the `LeagueProperties` version
+    pub fn get_best_of_overrides(&self) -> Vec<BestOfOverride> {
+        match self {
+            UpgradeableLeagueProperties::V1(prop) => {
+                LeagueProperties::from(prop.clone()).best_of_overrides
+            }
+            UpgradeableLeagueProperties::V2(prop) => prop.best_of_overrides.clone(),
+        }
+    }
+
+    /// Convenient implementation to get the `strict_schedule` value independant of the `LeagueProperties` version
+    pub fn get_strict_schedule(&self) -> bool {
+        match self {
+            UpgradeableLeagueProperties::V1(prop) => {
+                LeagueProperties::from(prop.clone()).strict_schedule
+            }
+            UpgradeableLeagueProperties::V2(prop) => prop.strict_schedule,
+        }
+    }
+
+    /// Convenient implementation to get the `deadline` value independant of the `LeagueProperties` version
+    pub fn get_deadline(&self) -> Option<u64> {
+        match self {
+            UpgradeableLeagueProperties::V1(prop) => LeagueProperties::from(prop.clone()).deadline,
+            UpgradeableLeagueProperties::V2(prop) => prop.deadline,
+        }
+    }
+
+    /// Convenient implementation to get the `matchday_deadlines` value independant of the `LeagueProperties` version
+    pub fn get_matchday_deadlines(&self) -> Vec<u64> {
+        match self {
+            UpgradeableLeagueProperties::V1(prop) => {
+                LeagueProperties::from(prop.clone()).matchday_deadlines
+            }
+            UpgradeableLeagueProperties::V2(prop) => prop.matchday_deadlines.clone(),
+        }
+    }
+
+    /// Convenient implementation to get the `forfeit_policy` value independant of the `LeagueProperties` version
+    pub fn get_forfeit_policy(&self) -> ForfeitPolicy {
+        match self {
+            UpgradeableLeagueProperties::V1(prop) => {
+                LeagueProperties::from(prop.clone()).forfeit_policy
+            }
+            UpgradeableLeagueProperties::V2(prop) => prop.forfeit_policy,
+        }
+    }
+
+    /// Convenient implementation to get the `withdrawal_policy` value independant of the `LeagueProperties` version
+    pub fn get_withdrawal_policy(&self) -> WithdrawalPolicy {
+        match self {
+            UpgradeableLeagueProperties::V1(prop) => {
+                LeagueProperties::from(prop.clone()).withdrawal_policy
+            }
+            UpgradeableLeagueProperties::V2(prop) => prop.withdrawal_policy,
+        }
+    }
+
+    /// Convenient implementation to get the `registration` value independant of the `LeagueProperties` version
+    pub fn get_registration(&self) -> Option<Registration> {
+        match self {
+            UpgradeableLeagueProperties::V1(prop) => {
+                LeagueProperties::from(prop.clone()).registration
+            }
+            UpgradeableLeagueProperties::V2(prop) => prop.registration.clone(),
+        }
+    }
+
+    /// Assign the players to their `groups` once the roster is complete
+    ///
+    /// The old properties have no groups, so they are upgraded first.
+    pub fn set_groups(&mut self, groups: Vec<u8>) {
+        if let UpgradeableLeagueProperties::V1(prop) = self {
+            *self = UpgradeableLeagueProperties::V2(LeagueProperties::from(prop.clone()));
+        }
+        if let UpgradeableLeagueProperties::V2(prop) = self {
+            prop.groups = groups;
+        }
+    }
+
+    /// Convenient implementation to get the `format` value independant of the `LeagueProperties` version
+    pub fn get_format(&self) -> LeagueFormat {
+        match self {
+            UpgradeableLeagueProperties::V1(prop) => LeagueProperties::from(prop.clone()).format,
+            UpgradeableLeagueProperties::V2(prop) => prop.format,
+        }
+    }
+}
+
+/// Description who the winner is if he exists
+///
+/// A match can also end in a `Draw` depending on the `DrawRule`.
+/// If both players forfeited, the match is finished with `NoWinner`.
+/// A match still going on is `Undecided`.
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Clone, Copy, PartialEq)]
+pub enum Winner {
+    FirstPlayer,
+    SecondPlayer,
+    Draw,
+    Undecided,
+    NoWinner,
+}
+
+impl Winner {
+    /// Check if a winner exists
+    ///
+    /// A draw is a result as well.
+    /// If not it means that the game match is still ongoing.
+    pub fn exist(&self) -> bool {
+        !matches!(self, Winner::Undecided)
+    }
+}
+
+/// The match between two contestants.
+///
+/// This contains all games where the max is given by the league properties (`best_of`)
+/// or one of its overrides for the stage, the round or the match.
+/// The GameVariants type must be the same as in the league properties (`game_type`)
+/// The pair of the contestants is given by the `MatchKey` typed key.
+#[derive(BorshDeserialize, BorshSerialize)]
+pub struct GameMatch {
+    /// The vector containing the games
+    games: Vec<Game>,
+    /// The maximum amount of games, resolved from the league's overrides when the match was created
+    best_of: u8,
+    /// Set if the match was decided by a forfeit instead of its games
+    forfeit: Option<Forfeit>,
+    /// A game reported by a player waiting for the confirmation of the opponent
+    pending: Option<PendingGame>,
+}
+
+impl GameMatch {
+    /// Create a new empty game match
+    pub fn new(best_of: u8) -> Self {
+        GameMatch {
+            games: Vec::new(),
+            best_of,
+            forfeit: None,
+            pending: None,
+        }
+    }
+
+    /// Get the maximum amount of games of this match
+    pub fn best_of(&self) -> u8 {
+        self.best_of
+    }
+
+    /// Finish the match by a forfeit
+    ///
+    /// The games played so far are kept, but the `winner` is given by the forfeit.
+    pub fn forfeit(&mut self, winner: Winner, reason: ForfeitReason) {
+        self.forfeit = Some(Forfeit { winner, reason });
+        self.pending = None;
+    }
+
+    /// Let the other player win the forfeit
+    ///
+    /// If both forfeited or there is no forfeit nothing changes.
+    pub fn overturn_forfeit(&mut self) {
+        if let Some(forfeit) = &mut self.forfeit {
+            forfeit.winner = match forfeit.winner {
+                Winner::FirstPlayer => Winner::SecondPlayer,
+                Winner::SecondPlayer => Winner::FirstPlayer,
+                other => other,
+            };
+        }
+    }
+
+    /// Lift the forfeit, so the match has to be played
+    pub fn clear_forfeit(&mut self) {
+        self.forfeit = None;
+    }
+
+    /// Get the forfeit if the match was decided by one
+    pub fn get_forfeit(&self) -> Option<&Forfeit> {
+        self.forfeit.as_ref()
+    }
+
+    /// Return the winner of a game match
+    ///
+    /// This checks each game and returns the winner according to the ''best of'' rules.
+    /// The winner can also be not determined yet due to missing games.
+    /// Drawn games either count half a point for both or are replayed, given by the `draw_rule`.
+    pub fn winner(&self, best_of: u8, draw_rule: DrawRule) -> Winner {
+        if let Some(forfeit) = &self.forfeit {
+            return forfeit.winner;
+        }
+        self.winner_after(self.games.len(), best_of, draw_rule)
+    }
+
+    /// Return the winner after the first `played` games of the match
+    fn winner_after(&self, played: usize, best_of: u8, draw_rule: DrawRule) -> Winner {
+        let games = &self.games[..played];
+        match draw_rule {
+            DrawRule::HalfPoint => {
+                // Count in half points to stay with integers
+                let best_of = best_of as u16;
+                let mut a = 0;
+                let mut b = 0;
+                for game in games.iter().take(best_of as usize) {
+                    match game.outcome() {
+                        GameOutcome::FirstPlayerWon => a += 2,
+                        GameOutcome::SecondPlayerWon => b += 2,
+                        GameOutcome::Draw => {
+                            a += 1;
+                            b += 1;
+                        }
+                    }
+                }
+                // More than half of all points cannot be caught up anymore
+                if a > best_of {
+                    return Winner::FirstPlayer;
+                }
+                if b > best_of {
+                    return Winner::SecondPlayer;
+                }
+                if games.len() >= best_of as usize {
+                    return Winner::Draw;
+                }
+                Winner::Undecided
+            }
+            DrawRule::Replay => {
+                let (a, b) = score_of(games);
+
+                let win_condition = best_of.div_ceil(2);
+
+                if a == win_condition {
+                    return Winner::FirstPlayer;
+                }
+                if b == win_condition {
+                    return Winner::SecondPlayer;
+                }
+                Winner::Undecided
+            }
+        }
+    }
+
+    /// Return the games won by each player
+    ///
+    /// Drawn games are not counted for anybody.
+    pub fn score(&self) -> (u8, u8) {
+        score_of(&self.games)
+    }
+
+    /// Check if the match was already decided before its last game
+    ///
+    /// Such a match would have games which should never have been played.
+    pub fn decided_early(&self, best_of: u8, draw_rule: DrawRule) -> bool {
+        (1..self.games.len()).any(|played| self.winner_after(played, best_of, draw_rule).exist())
+    }
+
+    /// Get the number of games in the match
+    pub fn game_count(&self) -> usize {
+        self.games.len()
+    }
+
+    /// Replace the game at the `index` and return the original one
+    pub fn replace_game(&mut self, index: usize, game: Game) -> Game {
+        std::mem::replace(&mut self.games[index], game)
+    }
+
+    /// Get the game at the `index`
+    pub fn game(&self, index: usize) -> &Game {
+        &self.games[index]
+    }
+
+    /// Remove the game at the `index` and return it, the later games move up
+    pub fn remove_game(&mut self, index: usize) -> Game {
+        self.games.remove(index)
+    }
+
+    /// Remove the last game and return it
+    pub fn remove_last_game(&mut self) -> Option<Game> {
+        self.games.pop()
+    }
+
+    /// Note a game reported by the `reporter` until the opponent confirms it
+    pub fn report(&mut self, game: Game, reporter: u8) {
+        self.pending = Some(PendingGame { game, reporter });
+    }
+
+    /// Get the pending game if there is one
+    pub fn get_pending(&self) -> Option<&PendingGame> {
+        self.pending.as_ref()
+    }
+
+    /// Add the pending game to the games of the match
+    pub fn confirm_pending(&mut self) {
+        if let Some(pending) = self.pending.take() {
+            self.games.push(pending.game);
+        }
+    }
+
+    /// Drop the pending game without adding it
+    pub fn discard_pending(&mut self) {
+        self.pending = None;
     }
 
     /// Add a new game
@@ -347,7 +3020,7 @@ impl GameMatch {
         self.games
             .iter()
             .map(|x| HelperGame {
-                first_player_is_winner: x.first_player_won(),
+                outcome: x.outcome(),
                 data: x.game_content(&game_type),
             })
             .collect::<Vec<HelperGame>>()
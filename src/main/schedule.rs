@@ -0,0 +1,86 @@
+//! Matchdays of a league in a round robin format
+//!
+//! A round robin does not need an order of its matches, but a real league is played matchday by matchday.
+//! The matchdays are generated with the circle method:
+//! The last player keeps the seat while everybody else moves half way around the table each matchday.
+//! Moving half way instead of one seat alternates home and away like the Berger tables do.
+//! Like the bracket the schedule is never stored, as it only depends on the number of players.
+
+use near_sdk::json_types::U64;
+use serde::Serialize;
+
+/// A matchday of a round robin
+pub struct Matchday {
+    /// The player indices of every match on this matchday, the home player first
+    pub pairings: Vec<(u8, u8)>,
+    /// The player sitting out this matchday if the number of players is odd
+    pub bye: Option<u8>,
+    /// The meeting of the players on this matchday, i.e. the leg in a double round robin
+    pub series: u8,
+}
+
+/// Generate the matchdays of a round robin for the given amount of players
+///
+/// With an even number of players there are `players - 1` matchdays, otherwise `players`.
+/// The player in the upper half of the table is at home, only the fixed seat alternates every matchday.
+/// This way nobody plays more than twice in a row at home or away.
+pub fn round_robin(players: u8) -> Vec<Matchday> {
+    // An odd number of players gets a missing player, whoever faces him has the bye
+    let size = players as usize + players as usize % 2;
+    let mut circle = (0..size as u8).collect::<Vec<u8>>();
+    circle.rotate_right(1);
+    let mut matchdays = Vec::with_capacity(size - 1);
+    for round in 0..size - 1 {
+        let mut pairings = Vec::with_capacity(size / 2);
+        let mut bye = None;
+        for seat in 0..size / 2 {
+            let (mut home, mut away) = (circle[seat], circle[size - 1 - seat]);
+            if seat == 0 && round % 2 == 1 {
+                (home, away) = (away, home);
+            }
+            if away == players {
+                bye = Some(home);
+            } else if home == players {
+                bye = Some(away);
+            } else {
+                pairings.push((home, away));
+            }
+        }
+        matchdays.push(Matchday {
+            pairings,
+            bye,
+            series: 0,
+        });
+
+        // The fixed seat stays, everybody else moves half way around the table
+        circle[1..].rotate_right(size / 2);
+    }
+    matchdays
+}
+
+/// Generate the matchdays of a double round robin for the given amount of players
+///
+/// The first half is the round robin of the first leg.
+/// The second half mirrors it as second leg with swapped home and away.
+pub fn double_round_robin(players: u8) -> Vec<Matchday> {
+    let first_leg = round_robin(players);
+    let second_leg = first_leg
+        .iter()
+        .map(|matchday| Matchday {
+            pairings: matchday.pairings.iter().map(|(a, b)| (*b, *a)).collect(),
+            bye: matchday.bye,
+            series: 1,
+        })
+        .collect::<Vec<Matchday>>();
+    first_leg.into_iter().chain(second_leg).collect()
+}
+
+/// Helper struct for serialization of a matchday in the views
+#[derive(Serialize)]
+pub struct HelperMatchday {
+    pub matchday: u8,
+    pub pairings: Vec<(String, String)>,
+    pub bye: Option<String>,
+    /// The block timestamp in nanoseconds when the matchday has to be finished
+    pub deadline: Option<U64>,
+}
@@ -0,0 +1,335 @@
+//! Computation of the standings of a league
+//!
+//! The standings are derived from the finished matches only.
+//! How many points a match is worth is given by the `PointsScheme` of the league.
+//! Players with equal points are ordered by the league's list of `Tiebreaker`s.
+//! If they are still equal after all of them, the order of the players in the league decides.
+
+use near_sdk::borsh::{self, BorshDeserialize, BorshSerialize};
+use near_sdk::require;
+use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
+
+use crate::main::Winner;
+
+/// The points a player gets for a match
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone)]
+pub enum PointsScheme {
+    /// Fixed points for a won, drawn or lost match, e.g. 3/1/0
+    MatchResult { win: u8, draw: u8, loss: u8 },
+    /// Points depending on the map score of the match
+    ///
+    /// `win` and `loss` are indexed by the number of games the loser won.
+    /// E.g. 3/2/1/0 in a best of 3 is `win: [3, 2]` and `loss: [0, 1]`.
+    /// If the index is out of range, the last entry is used.
+    MapScore {
+        win: Vec<u8>,
+        draw: u8,
+        loss: Vec<u8>,
+    },
+}
+
+impl Default for PointsScheme {
+    fn default() -> Self {
+        PointsScheme::MatchResult {
+            win: 3,
+            draw: 1,
+            loss: 0,
+        }
+    }
+}
+
+impl PointsScheme {
+    /// Check if the scheme can be applied to any match
+    pub fn assert_valid(&self) {
+        if let PointsScheme::MapScore { win, loss, .. } = self {
+            require!(
+                !win.is_empty() && !loss.is_empty(),
+                "Points for a map score need at least one entry for win and loss"
+            );
+        }
+    }
+
+    /// Points of the winner and the loser of a match where the loser won `loser_games`
+    fn points(&self, loser_games: u8) -> (u32, u32) {
+        match self {
+            PointsScheme::MatchResult { win, loss, .. } => (*win as u32, *loss as u32),
+            PointsScheme::MapScore { win, loss, .. } => {
+                let index = loser_games as usize;
+                let w = win.get(index).or_else(|| win.last()).unwrap();
+                let l = loss.get(index).or_else(|| loss.last()).unwrap();
+                (*w as u32, *l as u32)
+            }
+        }
+    }
+
+    /// Points of both players of a drawn match
+    fn draw_points(&self) -> u32 {
+        match self {
+            PointsScheme::MatchResult { draw, .. } => *draw as u32,
+            PointsScheme::MapScore { draw, .. } => *draw as u32,
+        }
+    }
+}
+
+/// A criterion to order players with equal points
+#[derive(BorshDeserialize, BorshSerialize, Serialize, Deserialize, Clone, Copy, PartialEq)]
+pub enum Tiebreaker {
+    /// Points from the matches between the tied players only
+    HeadToHead,
+    /// Games won minus games lost
+    GameDifference,
+    /// Games won
+    GamesWon,
+    /// The sum of the points of all opponents
+    Buchholz,
+    /// The sum of the points of all beaten opponents and half of the drawn ones
+    SonnebornBerger,
+}
+
+/// The default order of the tiebreakers
+pub fn default_tiebreakers() -> Vec<Tiebreaker> {
+    vec![
+        Tiebreaker::HeadToHead,
+        Tiebreaker::GameDifference,
+        Tiebreaker::GamesWon,
+    ]
+}
+
+/// The default order of the tiebreakers in a swiss league
+///
+/// The strength of the opponents matters more there as not everybody played against everybody.
+pub fn swiss_tiebreakers() -> Vec<Tiebreaker> {
+    vec![Tiebreaker::Buchholz, Tiebreaker::SonnebornBerger]
+}
+
+/// The result of a finished match as input for the standings
+pub struct FinishedMatch {
+    pub first: u8,
+    pub second: u8,
+    pub winner: Winner,
+    /// Whether the match was decided by a forfeit
+    pub forfeit: bool,
+    /// The games won by the first player
+    pub first_games: u8,
+    /// The games won by the second player
+    pub second_games: u8,
+}
+
+/// A row in the standings
+#[derive(Serialize, Clone)]
+pub struct StandingsEntry {
+    pub rank: u16,
+    pub player: String,
+    pub matches_played: u16,
+    pub matches_won: u16,
+    pub matches_drawn: u16,
+    pub matches_lost: u16,
+    pub games_won: u32,
+    pub games_lost: u32,
+    pub game_difference: i32,
+    pub points: u32,
+    /// Matches won because the opponent forfeited
+    pub walkovers: u16,
+    /// Matches lost by an own forfeit
+    pub forfeits: u16,
+    /// Whether the player withdrew from the league
+    pub withdrawn: bool,
+}
+
+impl StandingsEntry {
+    fn new(player: String) -> Self {
+        StandingsEntry {
+            rank: 0,
+            player,
+            matches_played: 0,
+            matches_won: 0,
+            matches_drawn: 0,
+            matches_lost: 0,
+            games_won: 0,
+            games_lost: 0,
+            game_difference: 0,
+            points: 0,
+            walkovers: 0,
+            forfeits: 0,
+            withdrawn: false,
+        }
+    }
+
+    /// Note a match from the view of this player
+    fn add(&mut self, result: &MatchPoints) {
+        self.matches_played += 1;
+        match result.own_result {
+            Ordering::Greater => self.matches_won += 1,
+            Ordering::Equal => self.matches_drawn += 1,
+            Ordering::Less => self.matches_lost += 1,
+        }
+        self.games_won += result.own_games as u32;
+        self.games_lost += result.other_games as u32;
+        self.game_difference = self.games_won as i32 - self.games_lost as i32;
+        self.points += result.points;
+        if result.forfeit {
+            match result.own_result {
+                Ordering::Greater => self.walkovers += 1,
+                _ => self.forfeits += 1,
+            }
+        }
+    }
+
+    /// Note a bye, which counts as a won match without games
+    fn add_bye(&mut self, points: u32) {
+        self.matches_played += 1;
+        self.matches_won += 1;
+        self.points += points;
+    }
+}
+
+/// A match from the view of one player
+struct MatchPoints {
+    opponent: usize,
+    own_result: Ordering,
+    own_games: u8,
+    other_games: u8,
+    points: u32,
+    forfeit: bool,
+}
+
+/// Compute the ordered standings of all `players`
+///
+/// `byes` lists the player indices once for every bye they got.
+pub fn compute_standings(
+    players: Vec<String>,
+    results: &[FinishedMatch],
+    byes: &[u8],
+    scheme: &PointsScheme,
+    tiebreakers: &[Tiebreaker],
+) -> Vec<StandingsEntry> {
+    // Every match from the view of both players
+    let mut per_player: Vec<Vec<MatchPoints>> = players.iter().map(|_| Vec::new()).collect();
+    for result in results {
+        let (first_points, second_points, first_result, second_result) = match result.winner {
+            Winner::FirstPlayer => {
+                let (w, l) = scheme.points(result.second_games);
+                (w, l, Ordering::Greater, Ordering::Less)
+            }
+            Winner::SecondPlayer => {
+                let (w, l) = scheme.points(result.first_games);
+                (l, w, Ordering::Less, Ordering::Greater)
+            }
+            Winner::Draw => (
+                scheme.draw_points(),
+                scheme.draw_points(),
+                Ordering::Equal,
+                Ordering::Equal,
+            ),
+            // Both forfeited, so both lost without any game
+            Winner::NoWinner => {
+                let (_, l) = scheme.points(0);
+                (l, l, Ordering::Less, Ordering::Less)
+            }
+            Winner::Undecided => continue,
+        };
+        per_player[result.first as usize].push(MatchPoints {
+            opponent: result.second as usize,
+            own_result: first_result,
+            own_games: result.first_games,
+            other_games: result.second_games,
+            points: first_points,
+            forfeit: result.forfeit,
+        });
+        per_player[result.second as usize].push(MatchPoints {
+            opponent: result.first as usize,
+            own_result: second_result,
+            own_games: result.second_games,
+            other_games: result.first_games,
+            points: second_points,
+            forfeit: result.forfeit,
+        });
+    }
+
+    let mut entries = players
+        .into_iter()
+        .enumerate()
+        .map(|(idx, player)| {
+            let mut entry = StandingsEntry::new(player);
+            for result in per_player[idx].iter() {
+                entry.add(result);
+            }
+            for _ in byes.iter().filter(|bye| **bye as usize == idx) {
+                entry.add_bye(scheme.points(0).0);
+            }
+            entry
+        })
+        .collect::<Vec<StandingsEntry>>();
+
+    // First order by points, the tiebreakers only apply inside a group of equal points
+    let mut order = (0..entries.len()).collect::<Vec<usize>>();
+    order.sort_by(|a, b| entries[*b].points.cmp(&entries[*a].points));
+    let mut ranked = Vec::new();
+    for group in split_by_key(order, |idx| entries[idx].points as i64) {
+        ranked.extend(break_ties(group, tiebreakers, &entries, &per_player));
+    }
+
+    for (rank, idx) in ranked.iter().enumerate() {
+        entries[*idx].rank = rank as u16 + 1;
+    }
+    entries.sort_by_key(|e| e.rank);
+    entries
+}
+
+/// Split an already sorted list into groups of equal keys
+fn split_by_key<F: Fn(usize) -> i64>(sorted: Vec<usize>, key: F) -> Vec<Vec<usize>> {
+    let mut groups: Vec<Vec<usize>> = Vec::new();
+    for idx in sorted {
+        match groups.last_mut() {
+            Some(group) if key(group[0]) == key(idx) => group.push(idx),
+            _ => groups.push(vec![idx]),
+        }
+    }
+    groups
+}
+
+/// Order a group of tied players by the first tiebreaker and the rest recursively
+///
+/// The deterministic fallback is the order of the players in the league.
+fn break_ties(
+    mut group: Vec<usize>,
+    tiebreakers: &[Tiebreaker],
+    entries: &[StandingsEntry],
+    per_player: &[Vec<MatchPoints>],
+) -> Vec<usize> {
+    if group.len() < 2 || tiebreakers.is_empty() {
+        group.sort_unstable();
+        return group;
+    }
+    let key = |idx: usize| -> i64 {
+        match tiebreakers[0] {
+            Tiebreaker::HeadToHead => per_player[idx]
+                .iter()
+                .filter(|m| group.contains(&m.opponent))
+                .map(|m| m.points as i64)
+                .sum(),
+            Tiebreaker::GameDifference => entries[idx].game_difference as i64,
+            Tiebreaker::GamesWon => entries[idx].games_won as i64,
+            Tiebreaker::Buchholz => per_player[idx]
+                .iter()
+                .map(|m| entries[m.opponent].points as i64)
+                .sum(),
+            // Counted in half points to stay with integers
+            Tiebreaker::SonnebornBerger => per_player[idx]
+                .iter()
+                .map(|m| match m.own_result {
+                    Ordering::Greater => 2 * entries[m.opponent].points as i64,
+                    Ordering::Equal => entries[m.opponent].points as i64,
+                    Ordering::Less => 0,
+                })
+                .sum(),
+        }
+    };
+    let mut sorted = group.clone();
+    sorted.sort_by_key(|idx| std::cmp::Reverse(key(*idx)));
+    split_by_key(sorted, key)
+        .into_iter()
+        .flat_map(|sub| break_ties(sub, &tiebreakers[1..], entries, per_player))
+        .collect()
+}
@@ -0,0 +1,125 @@
+//! Pairings of a league in the swiss format
+//!
+//! In a swiss league nobody plays against everybody.
+//! Instead a fixed number of rounds is played and each round pairs players with similar scores.
+//! Unlike a bracket the pairings depend on the order of the standings at the time they are made,
+//! so the rounds are stored once they are paired.
+
+use near_sdk::borsh::{self, BorshDeserialize, BorshSerialize};
+use serde::Serialize;
+
+/// The most steps the backtracking of a pairing may take
+///
+/// Without a limit a round without any pairing would try every combination of the players.
+pub const PAIRING_STEPS: u32 = 5_000;
+
+/// A round of a swiss league
+#[derive(BorshDeserialize, BorshSerialize)]
+pub struct SwissRound {
+    /// The player indices of every match in this round
+    pub pairings: Vec<(u8, u8)>,
+    /// The player sitting out this round if the number of players is odd
+    pub bye: Option<u8>,
+}
+
+impl SwissRound {
+    /// Check if both players are paired in this round in any order
+    pub fn pairs(&self, a: u8, b: u8) -> bool {
+        self.pairings
+            .iter()
+            .any(|pairing| *pairing == (a, b) || *pairing == (b, a))
+    }
+}
+
+/// Pair the first round where no scores exist yet
+///
+/// The top half of the seeds plays against the bottom half, i.e. 1 vs 5, 2 vs 6 and so on
+/// with eight players. With an odd number of players the last seed gets the bye.
+pub fn first_round(players: u8) -> SwissRound {
+    let bye = if players % 2 == 1 {
+        Some(players - 1)
+    } else {
+        None
+    };
+    let half = players / 2;
+    SwissRound {
+        pairings: (0..half).map(|seed| (seed, seed + half)).collect(),
+        bye,
+    }
+}
+
+/// Pair the next round from the current `ranking` of the player indices
+///
+/// The bye goes to the lowest ranked player who did not have one yet.
+/// Then the highest ranked player is paired with the next one in the ranking who is no rematch.
+/// If the rest cannot be paired anymore, the one after is tried.
+/// `played` tells if two players already met.
+///
+/// Returns `None` if no pairing without a rematch exists or none was found in `PAIRING_STEPS`.
+pub fn next_round<F>(ranking: &[u8], had_bye: &[u8], played: F) -> Option<SwissRound>
+where
+    F: Fn(u8, u8) -> bool,
+{
+    let mut steps = PAIRING_STEPS;
+    if ranking.len() % 2 == 1 {
+        return ranking
+            .iter()
+            .rev()
+            .filter(|player| !had_bye.contains(player))
+            .find_map(|bye| {
+                let rest = ranking
+                    .iter()
+                    .filter(|player| *player != bye)
+                    .copied()
+                    .collect::<Vec<u8>>();
+                pair(&rest, &played, &mut steps).map(|pairings| SwissRound {
+                    pairings,
+                    bye: Some(*bye),
+                })
+            });
+    }
+    pair(ranking, &played, &mut steps).map(|pairings| SwissRound {
+        pairings,
+        bye: None,
+    })
+}
+
+/// Pair all `remaining` players in their order without any rematch
+///
+/// This is a simple backtracking, which gives up as soon as it used up its `steps`.
+/// A pairing usually succeeds at once, only hopeless ones would try everything.
+fn pair<F>(remaining: &[u8], played: &F, steps: &mut u32) -> Option<Vec<(u8, u8)>>
+where
+    F: Fn(u8, u8) -> bool,
+{
+    if *steps == 0 {
+        return None;
+    }
+    *steps -= 1;
+    let (first, others) = match remaining.split_first() {
+        None => return Some(Vec::new()),
+        Some(split) => split,
+    };
+    others
+        .iter()
+        .filter(|opponent| !played(*first, **opponent))
+        .find_map(|opponent| {
+            let rest = others
+                .iter()
+                .filter(|player| *player != opponent)
+                .copied()
+                .collect::<Vec<u8>>();
+            pair(&rest, played, steps).map(|mut pairings| {
+                pairings.insert(0, (*first, *opponent));
+                pairings
+            })
+        })
+}
+
+/// Helper struct for serialization of a swiss round in the views
+#[derive(Serialize)]
+pub struct HelperSwissRound {
+    pub round: u8,
+    pub pairings: Vec<(String, String)>,
+    pub bye: Option<String>,
+}